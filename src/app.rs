@@ -0,0 +1,1454 @@
+//! Application flow: a stack of screens (menus, the match, pause, game over)
+//! driven by one fixed-rate loop. The top of the stack gets the input and is
+//! drawn; leaving a screen returns to the one below it.
+
+use crate::debug::FrameStats;
+use crate::draw::{compose_screen, draw_board};
+use crate::framebuffer::{FrameBuffer, Layers};
+use crate::game_loop::Match;
+use crate::game_session::{GameSession, PAUSE_MENU};
+use crate::hud::Hud;
+use crate::input::{configure_input, poll_input, poll_key_presses, poll_menu, InputState, MenuKey};
+use crate::model::{BallEvent, Board, PaddleHitLocation, Side, BREAKOUT_LEVELS};
+use crate::records::{Records, RECORDS_FILE};
+use crate::rules::{
+    GameRules, Handicap, ASSIST_RANGE, BALL_SPEED_RANGE, FPS_CHOICES, HANDICAP_HEIGHT_RANGE,
+    HANDICAP_SPEED_RANGE, MOMENTUM_RANGE, PADDLE_HEIGHT_RANGE, PADDLE_SPEED_RANGE, POINTS_RANGE,
+    START_SCORE_RANGE,
+};
+use crate::settings::{key_label, Action, Settings};
+use crate::stats::format_clock;
+use crate::terminal::RenderStyle;
+use crate::theme::{Theme, THEMES_DIR};
+use crate::widgets::{KeyHints, Menu, Panel, Widget};
+use crossterm::event::KeyCode;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAIN_MENU: [&str; 3] = ["Play", "Settings", "Quit"];
+const GAME_OVER_MENU: [&str; 3] = ["Rematch (swap sides)", "Main menu", "Quit"];
+
+/// One row of the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsRow {
+    Theme,
+    Preset,
+    PaddleHeight,
+    PaddleSpeed,
+    BallSpeed,
+    PointsToWin,
+    Fps,
+    Momentum,
+    Key(Action),
+    Back,
+}
+
+const SETTINGS_ROWS: [SettingsRow; 15] = [
+    SettingsRow::Theme,
+    SettingsRow::Preset,
+    SettingsRow::PaddleHeight,
+    SettingsRow::PaddleSpeed,
+    SettingsRow::BallSpeed,
+    SettingsRow::PointsToWin,
+    SettingsRow::Fps,
+    SettingsRow::Momentum,
+    SettingsRow::Key(Action::LeftUp),
+    SettingsRow::Key(Action::LeftDown),
+    SettingsRow::Key(Action::RightUp),
+    SettingsRow::Key(Action::RightDown),
+    SettingsRow::Key(Action::LeftServe),
+    SettingsRow::Key(Action::RightServe),
+    SettingsRow::Back,
+];
+
+/// One row of the match setup screen; handicap rows name the player (0 or 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetupRow {
+    Start,
+    PaddleHeight(usize),
+    PaddleSpeed(usize),
+    StartScore(usize),
+    Assist(usize),
+    Back,
+}
+
+const SETUP_ROWS: [SetupRow; 10] = [
+    SetupRow::Start,
+    SetupRow::PaddleHeight(0),
+    SetupRow::PaddleSpeed(0),
+    SetupRow::StartScore(0),
+    SetupRow::Assist(0),
+    SetupRow::PaddleHeight(1),
+    SetupRow::PaddleSpeed(1),
+    SetupRow::StartScore(1),
+    SetupRow::Assist(1),
+    SetupRow::Back,
+];
+
+/// Setup for single-player modes: only Player 1 plays, and without a head
+/// start, since the score there is their own result.
+const SOLO_SETUP_ROWS: [SetupRow; 5] = [
+    SetupRow::Start,
+    SetupRow::PaddleHeight(0),
+    SetupRow::PaddleSpeed(0),
+    SetupRow::Assist(0),
+    SetupRow::Back,
+];
+
+/// Step a signed `value` like `step_in_range`.
+fn step_offset(value: i32, delta: i32, (min, max): (i32, i32), wrap: bool) -> i32 {
+    let range = (0, (max - min) as usize);
+    step_in_range((value - min) as usize, delta, range, wrap) as i32 + min
+}
+
+/// Step `value` by `delta` within `min..=max`; `wrap` goes round instead of stopping.
+fn step_in_range(value: usize, delta: i32, (min, max): (usize, usize), wrap: bool) -> usize {
+    let next = value as i64 + delta as i64;
+    if next > max as i64 {
+        if wrap {
+            min
+        } else {
+            max
+        }
+    } else if next < min as i64 {
+        if wrap {
+            max
+        } else {
+            min
+        }
+    } else {
+        next as usize
+    }
+}
+
+/// Kinds of match offered on the mode select screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    /// First to the points-to-win setting wins.
+    Classic,
+    /// Games to the points-to-win setting, grouped into best-of-N sets.
+    Sets { best_of: u32 },
+    /// Highest score after a fixed time of play; a tie goes to sudden death.
+    Timed { minutes: u32 },
+    /// No score limit; play until you quit.
+    Endless,
+    /// One player against a back wall, counting consecutive returns.
+    Squash,
+    /// One player breaking bricks in front of a back wall, level by level.
+    Breakout,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 7] = [
+        GameMode::Classic,
+        GameMode::Sets { best_of: 3 },
+        GameMode::Sets { best_of: 5 },
+        GameMode::Timed { minutes: 3 },
+        GameMode::Endless,
+        GameMode::Squash,
+        GameMode::Breakout,
+    ];
+
+    pub fn label(self, rules: &GameRules) -> String {
+        match self {
+            GameMode::Classic => format!("Classic - first to {}", rules.points_to_win),
+            GameMode::Sets { best_of } => format!(
+                "Best of {best_of} sets - {} games to {}",
+                rules.games_per_set, rules.points_to_win
+            ),
+            GameMode::Timed { minutes } => format!("Timed - {minutes} minutes"),
+            GameMode::Endless => "Endless".to_string(),
+            GameMode::Squash => "Squash - wall practice".to_string(),
+            GameMode::Breakout => format!("Breakout - {BREAKOUT_LEVELS} levels of bricks"),
+        }
+    }
+
+    /// One player against a back wall, always on the left.
+    pub fn single_player(self) -> bool {
+        matches!(self, GameMode::Squash | GameMode::Breakout)
+    }
+
+    fn setup_rows(self) -> &'static [SetupRow] {
+        if self.single_player() {
+            &SOLO_SETUP_ROWS
+        } else {
+            &SETUP_ROWS
+        }
+    }
+
+    /// Score that wins the match, or each game when playing in sets.
+    pub fn target_score(self, points_to_win: u32) -> Option<u32> {
+        match self {
+            GameMode::Classic | GameMode::Sets { .. } => Some(points_to_win),
+            GameMode::Timed { .. } | GameMode::Endless | GameMode::Squash | GameMode::Breakout => {
+                None
+            }
+        }
+    }
+}
+
+/// One screen on the stack. Menu screens remember their selected row so
+/// returning to them keeps the cursor where it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    MainMenu {
+        selected: usize,
+    },
+    ModeSelect {
+        selected: usize,
+    },
+    /// Handicaps for the chosen mode, then start.
+    MatchSetup {
+        selected: usize,
+    },
+    Settings {
+        selected: usize,
+    },
+    InGame,
+    Paused {
+        selected: usize,
+    },
+    GameOver {
+        selected: usize,
+    },
+}
+
+impl Screen {
+    fn item_count(&self, mode: GameMode) -> usize {
+        match self {
+            Screen::MainMenu { .. } => MAIN_MENU.len(),
+            Screen::ModeSelect { .. } => GameMode::ALL.len(),
+            Screen::MatchSetup { .. } => mode.setup_rows().len(),
+            Screen::Settings { .. } => SETTINGS_ROWS.len(),
+            Screen::InGame => 0,
+            Screen::Paused { .. } => PAUSE_MENU.len(),
+            Screen::GameOver { .. } => GAME_OVER_MENU.len(),
+        }
+    }
+
+    fn selected_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Screen::MainMenu { selected }
+            | Screen::ModeSelect { selected }
+            | Screen::MatchSetup { selected }
+            | Screen::Settings { selected }
+            | Screen::Paused { selected }
+            | Screen::GameOver { selected } => Some(selected),
+            Screen::InGame => None,
+        }
+    }
+}
+
+/// The screen stack plus the match it may be showing.
+pub struct App {
+    screens: Vec<Screen>,
+    game: Option<Match>,
+    mode: GameMode,
+    /// Handicaps from the setup screen, Player 1 first.
+    handicaps: [Handicap; 2],
+    records: Records,
+    /// Where records are saved, next to the settings.
+    records_path: Option<PathBuf>,
+    settings: Settings,
+    /// Settings as saved: the loaded ones plus edits made here, without any
+    /// overrides for this run.
+    saved: Settings,
+    /// Where settings are saved; None keeps changes for this run only.
+    settings_path: Option<PathBuf>,
+    /// User themes, next to the settings; None offers the built-ins only.
+    themes_dir: Option<PathBuf>,
+    /// Settings edited since the settings screen was opened.
+    settings_changed: bool,
+    /// The settings screen is waiting for a key to bind to the selected row.
+    capturing: bool,
+    /// Message under the settings menu, e.g. a failed save.
+    settings_notice: Option<String>,
+    /// Menu screens are only redrawn after a change.
+    dirty: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(Settings::default(), None)
+    }
+}
+
+impl App {
+    /// Start at the main menu with `settings`, saving changes to `settings_path`.
+    pub fn new(settings: Settings, settings_path: Option<PathBuf>) -> Self {
+        let records_path = settings_path
+            .as_ref()
+            .map(|path| path.with_file_name(RECORDS_FILE));
+        // Unreadable records just start over; they are not worth a warning
+        let records = records_path
+            .as_ref()
+            .map(|path| Records::load_from(path).0)
+            .unwrap_or_default();
+        let themes_dir = settings_path
+            .as_ref()
+            .map(|path| path.with_file_name(THEMES_DIR));
+        Self {
+            screens: vec![Screen::MainMenu { selected: 0 }],
+            game: None,
+            mode: GameMode::Classic,
+            handicaps: [Handicap::default(); 2],
+            records,
+            records_path,
+            saved: settings.clone(),
+            settings,
+            settings_path,
+            themes_dir,
+            settings_changed: false,
+            capturing: false,
+            settings_notice: None,
+            dirty: true,
+        }
+    }
+
+    /// The current settings.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Play this run with `rules` without saving them. Rules edited on the
+    /// settings screen are saved as usual.
+    pub fn override_rules(&mut self, rules: GameRules) {
+        self.settings.rules = rules;
+    }
+
+    /// Name the theme this run is drawn with when it isn't the saved one, so
+    /// the settings screen shows it. It is saved only once changed there.
+    pub fn override_theme(&mut self, name: String) {
+        self.settings.theme = name;
+    }
+
+    /// True while the settings screen waits for a key to bind.
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// The screen on top, or None once the player quit.
+    pub fn screen(&self) -> Option<Screen> {
+        self.screens.last().copied()
+    }
+
+    /// Personal records, such as the best squash run.
+    pub fn records(&self) -> Records {
+        self.records
+    }
+
+    /// The match in progress, if any.
+    pub fn game(&self) -> Option<&Match> {
+        self.game.as_ref()
+    }
+
+    fn push(&mut self, screen: Screen) {
+        self.screens.push(screen);
+        self.dirty = true;
+    }
+
+    fn pop(&mut self) {
+        self.screens.pop();
+        self.dirty = true;
+        // The menu covered the board, so the match must redraw in full
+        if let (Some(Screen::InGame), Some(game)) = (self.screen(), self.game.as_mut()) {
+            game.invalidate();
+        }
+    }
+
+    fn replace_top(&mut self, screen: Screen) {
+        self.screens.pop();
+        self.push(screen);
+    }
+
+    fn return_to_main_menu(&mut self) {
+        self.record_squash_best();
+        self.screens = vec![Screen::MainMenu { selected: 0 }];
+        self.game = None;
+        self.dirty = true;
+    }
+
+    /// Start a match, with Player 1 on the right if `swapped`.
+    fn start_match(&mut self, mode: GameMode, swapped: bool) {
+        self.mode = mode;
+        let mut game = Match::new(
+            self.settings.rules,
+            mode.target_score(self.settings.rules.points_to_win),
+        );
+        // Handicaps belong to players, so they follow them across
+        let [first, second] = if mode.single_player() {
+            let solo = Handicap {
+                start_score: 0,
+                ..self.handicaps[0]
+            };
+            [solo, Handicap::default()]
+        } else {
+            self.handicaps
+        };
+        let swapped = swapped && !mode.single_player();
+        game.swapped = swapped;
+        if swapped {
+            game.set_handicaps(second, first);
+        } else {
+            game.set_handicaps(first, second);
+        }
+        match mode {
+            GameMode::Sets { best_of } => game.play_sets(best_of),
+            GameMode::Timed { minutes } => game.play_timed(minutes * 60),
+            GameMode::Squash => game.play_squash(self.records.squash_best),
+            GameMode::Breakout => game.play_breakout(),
+            GameMode::Classic | GameMode::Endless => {}
+        }
+        self.game = Some(game);
+        self.screens = vec![Screen::MainMenu { selected: 0 }, Screen::InGame];
+        self.dirty = true;
+    }
+
+    /// Keep a new best squash run once the run is over or the player leaves.
+    fn record_squash_best(&mut self) {
+        let Some(game) = self.game.as_ref().filter(|_| self.mode == GameMode::Squash) else {
+            return;
+        };
+        let best = game.board.score.right;
+        if best <= self.records.squash_best {
+            return;
+        }
+        self.records.squash_best = best;
+        if let Some(path) = &self.records_path {
+            // A failed save only loses the record, so play carries on
+            let _ = self.records.save_to(path);
+        }
+    }
+
+    /// Same mode again with the players on opposite sides.
+    fn rematch(&mut self) {
+        let swapped = self.game.as_ref().is_some_and(|g| g.swapped);
+        self.start_match(self.mode, !swapped);
+    }
+
+    /// One frame of play on the in-game screen.
+    pub fn play_frame(&mut self, input: InputState, elapsed: Duration) {
+        if input.quit {
+            self.return_to_main_menu();
+            return;
+        }
+        if input.pause {
+            self.push(Screen::Paused { selected: 0 });
+            return;
+        }
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        let event = game.tick(input, elapsed);
+        let won = game.winner().is_some();
+        if event == BallEvent::LeftGoal {
+            self.record_squash_best();
+        }
+        if won {
+            self.replace_top(Screen::GameOver { selected: 0 });
+        }
+    }
+
+    /// Handle a key on a menu screen.
+    pub fn handle_key<W: Write>(&mut self, key: MenuKey, session: &GameSession<W>) {
+        let Some(screen) = self.screens.last_mut() else {
+            return;
+        };
+        self.dirty = true;
+
+        let count = screen.item_count(self.mode);
+        if let Some(selected) = screen.selected_mut() {
+            match key {
+                MenuKey::Up => *selected = (*selected + count - 1) % count,
+                MenuKey::Down => *selected = (*selected + 1) % count,
+                _ => {}
+            }
+        }
+
+        match *screen {
+            Screen::MainMenu { selected } => match key {
+                MenuKey::Select => match selected {
+                    0 => self.push(Screen::ModeSelect { selected: 0 }),
+                    1 => self.push(Screen::Settings { selected: 0 }),
+                    _ => self.screens.clear(),
+                },
+                MenuKey::Back => self.screens.clear(),
+                _ => {}
+            },
+            Screen::ModeSelect { selected } => match key {
+                MenuKey::Select => {
+                    self.mode = GameMode::ALL[selected];
+                    self.push(Screen::MatchSetup { selected: 0 });
+                }
+                MenuKey::Back => self.pop(),
+                _ => {}
+            },
+            Screen::MatchSetup { selected } => match (key, self.mode.setup_rows()[selected]) {
+                (MenuKey::Select, SetupRow::Start) => self.start_match(self.mode, false),
+                (MenuKey::Back, _) | (MenuKey::Select, SetupRow::Back) => self.pop(),
+                (MenuKey::Left, row) => self.change_handicap(row, -1, false),
+                (MenuKey::Right, row) => self.change_handicap(row, 1, false),
+                (MenuKey::Select, row) => self.change_handicap(row, 1, true),
+                _ => {}
+            },
+            Screen::Settings { selected } => {
+                let row = SETTINGS_ROWS[selected];
+                match (key, row) {
+                    (MenuKey::Back, _) | (MenuKey::Select, SettingsRow::Back) => {
+                        self.leave_settings()
+                    }
+                    (MenuKey::Select, SettingsRow::Key(_)) => {
+                        self.capturing = true;
+                        self.settings_notice = None;
+                    }
+                    (MenuKey::Left, _) => self.change_setting(row, -1, false, session),
+                    (MenuKey::Right, _) => self.change_setting(row, 1, false, session),
+                    (MenuKey::Select, _) => self.change_setting(row, 1, true, session),
+                    _ => {}
+                }
+            }
+            Screen::Paused { selected } => match key {
+                MenuKey::Pause => self.pop(),
+                MenuKey::Select => match selected {
+                    0 => self.pop(),
+                    1 => self.push(Screen::Settings { selected: 0 }),
+                    _ => self.return_to_main_menu(),
+                },
+                MenuKey::Back => self.return_to_main_menu(),
+                MenuKey::Char('t') => {
+                    cycle_theme(session, self.themes_dir.as_deref(), true);
+                    self.settings.theme = session.theme_name();
+                    self.saved.theme = session.theme_name();
+                    self.save_settings();
+                }
+                _ => {}
+            },
+            Screen::GameOver { selected } => match key {
+                MenuKey::Select => match selected {
+                    0 => self.rematch(),
+                    1 => self.return_to_main_menu(),
+                    _ => self.screens.clear(),
+                },
+                MenuKey::Back => self.return_to_main_menu(),
+                _ => {}
+            },
+            Screen::InGame => {}
+        }
+    }
+
+    /// Bind the key pressed while capturing to the selected row's action.
+    /// Esc cancels.
+    pub fn capture_key(&mut self, code: KeyCode) {
+        let Some(Screen::Settings { selected }) = self.screen() else {
+            self.capturing = false;
+            return;
+        };
+        self.capturing = false;
+        self.dirty = true;
+        let SettingsRow::Key(action) = SETTINGS_ROWS[selected] else {
+            return;
+        };
+        if code == KeyCode::Esc {
+            return;
+        }
+        if self.settings.keys.assign(action, code) {
+            self.saved.keys = self.settings.keys;
+            self.settings_changed = true;
+        } else {
+            self.settings_notice = Some(format!("{} can't be bound", key_label(code)));
+        }
+    }
+
+    /// Adjust one handicap like a setting.
+    fn change_handicap(&mut self, row: SetupRow, delta: i32, wrap: bool) {
+        let as_usize = |(min, max): (u32, u32)| (min as usize, max as usize);
+        match row {
+            SetupRow::PaddleHeight(p) => {
+                let h = &mut self.handicaps[p];
+                h.paddle_height = step_offset(h.paddle_height, delta, HANDICAP_HEIGHT_RANGE, wrap)
+            }
+            SetupRow::PaddleSpeed(p) => {
+                let h = &mut self.handicaps[p];
+                h.paddle_speed = step_offset(h.paddle_speed, delta, HANDICAP_SPEED_RANGE, wrap)
+            }
+            SetupRow::StartScore(p) => {
+                let h = &mut self.handicaps[p];
+                let range = as_usize(START_SCORE_RANGE);
+                h.start_score = step_in_range(h.start_score as usize, delta, range, wrap) as u32
+            }
+            SetupRow::Assist(p) => {
+                let h = &mut self.handicaps[p];
+                let range = as_usize(ASSIST_RANGE);
+                h.assist = step_in_range(h.assist as usize, delta, range, wrap) as u32
+            }
+            SetupRow::Start | SetupRow::Back => {}
+        }
+    }
+
+    fn setup_row_text(&self, row: SetupRow) -> String {
+        let (label, value) = match row {
+            SetupRow::Start => return "Start match".to_string(),
+            SetupRow::Back => return "Back".to_string(),
+            SetupRow::PaddleHeight(p) => (
+                format!("P{} paddle height", p + 1),
+                format!("{:+}", self.handicaps[p].paddle_height),
+            ),
+            SetupRow::PaddleSpeed(p) => (
+                format!("P{} paddle speed", p + 1),
+                format!("{:+}", self.handicaps[p].paddle_speed),
+            ),
+            SetupRow::StartScore(p) => (
+                format!("P{} head start", p + 1),
+                self.handicaps[p].start_score.to_string(),
+            ),
+            SetupRow::Assist(p) => (
+                format!("P{} assist", p + 1),
+                match self.handicaps[p].assist {
+                    0 => "off".to_string(),
+                    level => level.to_string(),
+                },
+            ),
+        };
+        format!("{label:<18}< {value} >")
+    }
+
+    /// Adjust one setting; Enter wraps around, the arrows stop at the ends.
+    fn change_setting<W: Write>(
+        &mut self,
+        row: SettingsRow,
+        delta: i32,
+        wrap: bool,
+        session: &GameSession<W>,
+    ) {
+        let s = &mut self.settings.rules;
+        match row {
+            SettingsRow::Theme => {
+                cycle_theme(session, self.themes_dir.as_deref(), delta > 0);
+                self.settings.theme = session.theme_name();
+                self.saved.theme = session.theme_name();
+            }
+            SettingsRow::Preset => {
+                let count = GameRules::PRESETS.len();
+                // Custom rules sit just before the first preset
+                let index = match s.preset_name() {
+                    Some(name) => GameRules::PRESETS.iter().position(|(n, _)| *n == name),
+                    None => None,
+                };
+                let next = match index {
+                    Some(i) => step_in_range(i, delta, (0, count - 1), true),
+                    None if delta > 0 => 0,
+                    None => count - 1,
+                };
+                *s = GameRules::PRESETS[next].1;
+            }
+            SettingsRow::PaddleHeight => {
+                s.paddle_height = step_in_range(s.paddle_height, delta, PADDLE_HEIGHT_RANGE, wrap)
+            }
+            SettingsRow::PaddleSpeed => {
+                s.paddle_speed = step_in_range(s.paddle_speed, delta, PADDLE_SPEED_RANGE, wrap)
+            }
+            SettingsRow::BallSpeed => {
+                // Shown as speed, stored as a divisor: faster means a smaller divisor
+                s.ball_speed_divisor =
+                    step_in_range(s.ball_speed_divisor, -delta, BALL_SPEED_RANGE, wrap)
+            }
+            SettingsRow::PointsToWin => {
+                let range = (POINTS_RANGE.0 as usize, POINTS_RANGE.1 as usize);
+                s.points_to_win = step_in_range(s.points_to_win as usize, delta, range, wrap) as u32
+            }
+            SettingsRow::Fps => {
+                let index = FPS_CHOICES.iter().position(|f| *f == s.fps).unwrap_or(0);
+                let index = step_in_range(index, delta, (0, FPS_CHOICES.len() - 1), wrap);
+                s.fps = FPS_CHOICES[index];
+            }
+            SettingsRow::Momentum => {
+                let range = (MOMENTUM_RANGE.0 as usize, MOMENTUM_RANGE.1 as usize);
+                s.momentum = step_in_range(s.momentum as usize, delta, range, wrap) as u8
+            }
+            SettingsRow::Key(_) | SettingsRow::Back => return,
+        }
+        // Edited rules are saved whole, replacing any override for this run
+        if row != SettingsRow::Theme {
+            self.saved.rules = self.settings.rules;
+        }
+        self.settings_changed = true;
+    }
+
+    /// Leave the settings screen, saving and applying any changes.
+    /// A match in progress picks up the new rules; its target score stays.
+    fn leave_settings(&mut self) {
+        if std::mem::take(&mut self.settings_changed) {
+            configure_input(self.settings.keys, self.settings.rules.momentum);
+            if let Some(game) = self.game.as_mut() {
+                game.set_rules(self.settings.rules);
+            }
+            self.save_settings();
+        }
+        self.pop();
+    }
+
+    fn save_settings(&mut self) {
+        let Some(path) = &self.settings_path else {
+            return;
+        };
+        self.settings_notice = self
+            .saved
+            .save_to(path)
+            .err()
+            .map(|err| format!("Could not save settings: {err}"));
+    }
+
+    /// Draw the top screen: the match every frame it changes, menus after a change.
+    pub fn render<W: Write>(
+        &mut self,
+        session: &GameSession<W>,
+        frame_stats: &FrameStats,
+    ) -> io::Result<()> {
+        let Some(screen) = self.screen() else {
+            return Ok(());
+        };
+        if screen == Screen::InGame {
+            if let Some(game) = self.game.as_mut() {
+                game.render(session, frame_stats)?;
+            }
+            return Ok(());
+        }
+        if !std::mem::take(&mut self.dirty) {
+            return Ok(());
+        }
+
+        if let (Screen::Paused { selected }, Some(game)) = (screen, self.game.as_ref()) {
+            return session.render_pause_menu(
+                &game.board,
+                &game.stats,
+                frame_stats.fps(),
+                selected,
+            );
+        }
+        let panel = self.menu_panel(screen);
+        session.render_frame(&self.compose_menu(panel, &session.style()))
+    }
+
+    /// Value column of a settings row.
+    fn settings_value(&self, row: SettingsRow) -> String {
+        let s = &self.settings.rules;
+        match row {
+            SettingsRow::Theme => format!("< {} >", self.settings.theme),
+            SettingsRow::Preset => format!("< {} >", s.preset_name().unwrap_or("custom")),
+            SettingsRow::PaddleHeight => format!("< {} >", s.paddle_height),
+            SettingsRow::PaddleSpeed => format!("< {} >", s.paddle_speed),
+            SettingsRow::BallSpeed => {
+                // Level 1 is the slowest divisor
+                format!("< {} >", BALL_SPEED_RANGE.1 + 1 - s.ball_speed_divisor)
+            }
+            SettingsRow::PointsToWin => format!("< {} >", s.points_to_win),
+            SettingsRow::Fps => format!("< {} >", s.fps),
+            SettingsRow::Momentum => format!("< {} >", s.momentum),
+            SettingsRow::Key(action) => key_label(self.settings.keys.get(action)),
+            SettingsRow::Back => String::new(),
+        }
+    }
+
+    fn settings_label(row: SettingsRow) -> &'static str {
+        match row {
+            SettingsRow::Theme => "Theme",
+            SettingsRow::Preset => "Rules",
+            SettingsRow::PaddleHeight => "Paddle height",
+            SettingsRow::PaddleSpeed => "Paddle speed",
+            SettingsRow::BallSpeed => "Ball speed",
+            SettingsRow::PointsToWin => "Points to win",
+            SettingsRow::Fps => "FPS",
+            SettingsRow::Momentum => "Momentum",
+            SettingsRow::Key(action) => action.label(),
+            SettingsRow::Back => "Back",
+        }
+    }
+
+    /// Panel for a menu screen other than pause.
+    fn menu_panel(&self, screen: Screen) -> Panel {
+        let hints = KeyHints::new(&[("↑/↓", "Move"), ("Enter", "Select"), ("Q", "Back")]);
+        let keys = &self.settings.keys;
+        match screen {
+            Screen::MainMenu { selected } => Panel::new("TERMINAL PONG")
+                .gap(1)
+                .with(Menu::new(&MAIN_MENU, selected))
+                .gap(1)
+                .line(format!(
+                    "{}/{}: left paddle   {}/{}: right paddle",
+                    key_label(keys.left_up),
+                    key_label(keys.left_down),
+                    key_label(keys.right_up),
+                    key_label(keys.right_down)
+                ))
+                .line(format!(
+                    "{}: left serve          {}: right serve",
+                    key_label(keys.left_serve),
+                    key_label(keys.right_serve)
+                ))
+                .line("Space: pause       Q: back to menu")
+                .footer(hints),
+            Screen::ModeSelect { selected } => {
+                let rules = &self.settings.rules;
+                let labels: Vec<String> = GameMode::ALL.iter().map(|m| m.label(rules)).collect();
+                let items: Vec<&str> = labels.iter().map(String::as_str).collect();
+                Panel::new("SELECT MODE")
+                    .gap(1)
+                    .with(Menu::new(&items, selected))
+                    .footer(hints)
+            }
+            Screen::MatchSetup { selected } => {
+                let rows: Vec<String> = (self.mode.setup_rows().iter())
+                    .map(|r| self.setup_row_text(*r))
+                    .collect();
+                let items: Vec<&str> = rows.iter().map(String::as_str).collect();
+                Panel::new("MATCH SETUP")
+                    .gap(1)
+                    .line(self.mode.label(&self.settings.rules))
+                    .gap(1)
+                    .with(Menu::new(&items, selected))
+                    .footer(KeyHints::new(&[
+                        ("←/→", "Handicap"),
+                        ("Enter", "Select"),
+                        ("Q", "Back"),
+                    ]))
+            }
+            Screen::Settings { selected } => {
+                let rows: Vec<String> = SETTINGS_ROWS
+                    .iter()
+                    .enumerate()
+                    .map(|(i, row)| {
+                        let value = if self.capturing && i == selected {
+                            "press a key...".to_string()
+                        } else {
+                            self.settings_value(*row)
+                        };
+                        format!("{:<15}{value}", Self::settings_label(*row))
+                            .trim_end()
+                            .to_string()
+                    })
+                    .collect();
+                let items: Vec<&str> = rows.iter().map(String::as_str).collect();
+                let mut panel = Panel::new("SETTINGS")
+                    .gap(1)
+                    .with(Menu::new(&items, selected));
+                if let Some(notice) = &self.settings_notice {
+                    panel = panel.gap(1).line(notice.clone());
+                }
+                panel.footer(KeyHints::new(&[
+                    ("←/→", "Change"),
+                    ("Enter", "Select"),
+                    ("Q", "Back"),
+                ]))
+            }
+            Screen::GameOver { selected } => match &self.game {
+                Some(game) => game_over_panel(game, selected),
+                None => Panel::new("GAME OVER").with(Menu::new(&GAME_OVER_MENU, selected)),
+            },
+            // Drawn by the match and the session
+            Screen::InGame | Screen::Paused { .. } => Panel::new(""),
+        }
+    }
+
+    /// Center a menu panel over the match board, or the idle board before a match.
+    fn compose_menu(&self, panel: Panel, style: &RenderStyle) -> FrameBuffer {
+        let backdrop = match &self.game {
+            Some(game) => game.board.clone(),
+            None => Board::new_static(),
+        };
+        let mut court = FrameBuffer::new(backdrop.width, backdrop.height, ' ');
+        draw_board(&mut court, &backdrop, style);
+        let mut fb = compose_screen(&court, &backdrop, &Hud::new(), style);
+
+        let menu = panel.render(style);
+        let (x, y) = fb.centered_origin(&menu);
+        let mut layers = Layers::new();
+        layers.push(1, x, y, menu);
+        layers.compose_onto(&mut fb);
+        fb
+    }
+}
+
+/// Winner, score and per-player statistics, then the game over menu.
+fn game_over_panel(game: &Match, selected: usize) -> Panel {
+    let stats = &game.stats;
+    let score = game.board.score;
+    let mut panel = Panel::new("GAME OVER").gap(1);
+    if let Some(side) = game.winner() {
+        panel = panel.line(format!(
+            "{} wins! ({} side)",
+            game.player_name(side),
+            side.label().to_lowercase()
+        ));
+    }
+    panel = panel.line(format!(
+        "Score {} - {}   Time {}   Longest rally {}",
+        score.left,
+        score.right,
+        format_clock(stats.play_time),
+        stats.longest_rally
+    ));
+    if let Some(sets) = &game.board.sets {
+        let games: Vec<String> = sets
+            .finished
+            .iter()
+            .map(|set| format!("{}-{}", set.left, set.right))
+            .collect();
+        panel = panel.line(format!(
+            "Sets {} - {}   ({})",
+            sets.sets.left,
+            sets.sets.right,
+            games.join(", ")
+        ));
+    }
+    if game.board.clock.is_some_and(|clock| clock.overtime) {
+        panel = panel.line("Decided in sudden-death overtime");
+    }
+    panel = panel
+        .gap(1)
+        .line(format!(
+            "{:<13}{:>14}{:>14}",
+            "",
+            game.player_name(Side::Left),
+            game.player_name(Side::Right)
+        ))
+        .line(format!(
+            "{:<13}{:>14}{:>14}",
+            "Paddle hits", stats.left.hits, stats.right.hits
+        ));
+    // Results under a handicap say so
+    if stats.left.handicap != Handicap::default() || stats.right.handicap != Handicap::default() {
+        panel = panel.line(format!(
+            "{:<13}{:>14}{:>14}",
+            "Handicap",
+            stats.left.handicap.summary(),
+            stats.right.handicap.summary()
+        ));
+    }
+    for location in PaddleHitLocation::ALL {
+        panel = panel.line(format!(
+            "  {:<11}{:>14}{:>14}",
+            location.label(),
+            stats.left.zone(location),
+            stats.right.zone(location)
+        ));
+    }
+    panel
+        .gap(1)
+        .with(Menu::new(&GAME_OVER_MENU, selected))
+        .footer(KeyHints::new(&[
+            ("↑/↓", "Move"),
+            ("Enter", "Select"),
+            ("Q", "Main menu"),
+        ]))
+}
+
+/// Switch the session to the next (or previous) theme, including user themes
+/// in `themes_dir`. Custom themes that fail to load are skipped.
+fn cycle_theme<W: Write>(session: &GameSession<W>, themes_dir: Option<&Path>, forward: bool) {
+    let names = Theme::available_in(themes_dir);
+    let current = session.theme_name();
+    let start = names.iter().position(|n| *n == current).unwrap_or(0);
+    for offset in 1..=names.len() {
+        let index = if forward {
+            (start + offset) % names.len()
+        } else {
+            (start + names.len() * 2 - offset) % names.len()
+        };
+        if let Ok(theme) = Theme::load_from(&names[index], themes_dir) {
+            session.set_theme(&theme);
+            return;
+        }
+    }
+}
+
+/// Run `app` from the main menu until the player quits, at the frame rate
+/// from its settings.
+pub fn run_app<W: Write>(session: &GameSession<W>, mut app: App) -> io::Result<()> {
+    configure_input(app.settings.keys, app.settings.rules.momentum);
+    let mut frame_stats = FrameStats::new();
+    let mut last_frame = Instant::now();
+
+    while let Some(screen) = app.screen() {
+        let frame_start = Instant::now();
+        let elapsed = frame_start.duration_since(last_frame);
+
+        // Input and update phase
+        if screen == Screen::InGame {
+            let input = poll_input()?;
+            app.play_frame(input, elapsed);
+        } else if app.is_capturing() {
+            if let Some(code) = poll_key_presses()?.into_iter().next() {
+                app.capture_key(code);
+            }
+        } else {
+            for key in poll_menu()? {
+                app.handle_key(key, session);
+            }
+        }
+
+        // Render phase
+        app.render(session, &frame_stats)?;
+
+        // Frame rate limiting
+        let frame_duration = app.settings().rules.frame_duration();
+        let work = frame_start.elapsed();
+        frame_stats.record(work, elapsed);
+        if work < frame_duration {
+            thread::sleep(frame_duration - work);
+        }
+        last_frame = frame_start;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::{ColorDepth, TerminalCapabilities};
+
+    fn session() -> GameSession<Vec<u8>> {
+        let caps = TerminalCapabilities {
+            unicode: false,
+            colors: ColorDepth::Monochrome,
+            synchronized_output: false,
+            kitty_keyboard: false,
+        };
+        GameSession::with_writer(Vec::new(), caps)
+    }
+
+    fn press(app: &mut App, session: &GameSession<Vec<u8>>, keys: &[MenuKey]) {
+        for key in keys {
+            app.handle_key(*key, session);
+        }
+    }
+
+    #[test]
+    fn main_menu_leads_to_a_match() {
+        let s = session();
+        let mut app = App::default();
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::ModeSelect { selected: 0 }));
+
+        press(&mut app, &s, &[MenuKey::Up; 3]);
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::MatchSetup { selected: 0 }));
+
+        // Two points head start for Player 2, then start from the top row
+        press(&mut app, &s, &[MenuKey::Down; 7]);
+        press(
+            &mut app,
+            &s,
+            &[MenuKey::Right, MenuKey::Right, MenuKey::Left],
+        );
+        press(&mut app, &s, &[MenuKey::Select]);
+        press(&mut app, &s, &[MenuKey::Up; 7]);
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::InGame));
+        let game = app.game().unwrap();
+        assert_eq!(game.target_score, None); // Endless
+        assert_eq!(game.board.score.right, 2);
+        assert_eq!(game.stats.right.handicap.start_score, 2);
+    }
+
+    #[test]
+    fn single_player_modes_only_handicap_player_one() {
+        let s = session();
+        let mut app = App::default();
+        app.handicaps[0].start_score = 3;
+        app.handicaps[1].assist = 2;
+        press(&mut app, &s, &[MenuKey::Select]);
+        press(&mut app, &s, &[MenuKey::Down; 5]);
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.mode, GameMode::Squash);
+        app.render(&s, &FrameStats::new()).unwrap();
+        let out = String::from_utf8(s.writer().clone()).unwrap();
+        assert!(out.contains("P1 assist"));
+        assert!(!out.contains("P2") && !out.contains("head start"));
+
+        // Assist for Player 1, then round past Back to Start
+        press(&mut app, &s, &[MenuKey::Down; 3]);
+        press(&mut app, &s, &[MenuKey::Right]);
+        press(&mut app, &s, &[MenuKey::Down; 2]);
+        press(&mut app, &s, &[MenuKey::Select]);
+        let game = app.game().unwrap();
+        assert_eq!(game.board.score.left, 0);
+        assert_eq!(game.board.left_handicap.assist, 1);
+        assert_eq!(game.stats.left.handicap.start_score, 0);
+        assert_eq!(game.stats.right.handicap, Handicap::default());
+
+        // A rematch keeps the player on the left with their handicap
+        app.rematch();
+        let game = app.game().unwrap();
+        assert!(!game.swapped);
+        assert_eq!(game.board.left_handicap.assist, 1);
+    }
+
+    #[test]
+    fn menus_wrap_and_back_returns_to_previous_screen() {
+        let s = session();
+        let mut app = App::default();
+        press(&mut app, &s, &[MenuKey::Up]);
+        assert_eq!(app.screen(), Some(Screen::MainMenu { selected: 2 }));
+
+        press(&mut app, &s, &[MenuKey::Up, MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::Settings { selected: 0 }));
+        press(&mut app, &s, &[MenuKey::Back]);
+        assert_eq!(app.screen(), Some(Screen::MainMenu { selected: 1 }));
+
+        // Quit from the main menu empties the stack
+        press(&mut app, &s, &[MenuKey::Down, MenuKey::Select]);
+        assert_eq!(app.screen(), None);
+    }
+
+    #[test]
+    fn pause_freezes_play_and_quit_returns_to_main_menu() {
+        let s = session();
+        let mut app = App::default();
+        app.start_match(GameMode::Classic, false);
+
+        let mut input = InputState::new();
+        input.pause = true;
+        app.play_frame(input, Duration::ZERO);
+        assert_eq!(app.screen(), Some(Screen::Paused { selected: 0 }));
+
+        // Space resumes, Q leaves the match
+        press(&mut app, &s, &[MenuKey::Pause]);
+        assert_eq!(app.screen(), Some(Screen::InGame));
+
+        let mut input = InputState::new();
+        input.quit = true;
+        app.play_frame(input, Duration::ZERO);
+        assert_eq!(app.screen(), Some(Screen::MainMenu { selected: 0 }));
+        assert!(app.game().is_none());
+    }
+
+    #[test]
+    fn pause_menu_opens_settings_and_returns() {
+        let s = session();
+        let mut app = App::default();
+        app.start_match(GameMode::Classic, false);
+        app.push(Screen::Paused { selected: 0 });
+
+        press(&mut app, &s, &[MenuKey::Down, MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::Settings { selected: 0 }));
+        press(&mut app, &s, &[MenuKey::Right]);
+        assert_eq!(s.theme_name(), "retro");
+        press(&mut app, &s, &[MenuKey::Back]);
+        assert_eq!(app.screen(), Some(Screen::Paused { selected: 1 }));
+    }
+
+    #[test]
+    fn settings_are_edited_saved_and_applied() {
+        let s = session();
+        let dir = std::env::temp_dir().join(format!("pong-settings-{}", std::process::id()));
+        let path = dir.join("settings.conf");
+        let mut app = App::new(Settings::default(), Some(path.clone()));
+        app.start_match(GameMode::Classic, false);
+        app.push(Screen::Settings { selected: 0 });
+
+        // Paddle speed up twice, ball speed up once
+        press(&mut app, &s, &[MenuKey::Down; 2]);
+        press(
+            &mut app,
+            &s,
+            &[
+                MenuKey::Down,
+                MenuKey::Right,
+                MenuKey::Right,
+                MenuKey::Down,
+                MenuKey::Right,
+            ],
+        );
+        // Rebind "Left up" to I
+        press(&mut app, &s, &[MenuKey::Down; 4]);
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert!(app.is_capturing());
+        app.render(&s, &FrameStats::new()).unwrap();
+        assert!(String::from_utf8_lossy(&s.writer()).contains("press a key..."));
+        app.capture_key(KeyCode::Char('I'));
+        assert!(!app.is_capturing());
+        press(&mut app, &s, &[MenuKey::Back]);
+
+        assert_eq!(app.screen(), Some(Screen::InGame));
+        let board = &app.game().unwrap().board;
+        assert_eq!((board.left.speed, board.rules.ball_speed_divisor), (3, 1));
+
+        let (saved, errors) = Settings::load_from(&path);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(saved, *app.settings());
+        assert_eq!(saved.keys.left_up, KeyCode::Char('i'));
+    }
+
+    #[test]
+    fn rules_override_is_saved_only_once_edited() {
+        let s = session();
+        let dir = std::env::temp_dir().join(format!("pong-override-{}", std::process::id()));
+        let path = dir.join("settings.conf");
+        let mut app = App::new(Settings::default(), Some(path.clone()));
+        app.override_rules(GameRules::ARCADE);
+        app.start_match(GameMode::Classic, false);
+        assert_eq!(app.game().unwrap().board.rules, GameRules::ARCADE);
+
+        // A theme change saves the theme but keeps the saved rules
+        app.push(Screen::Paused { selected: 0 });
+        press(&mut app, &s, &[MenuKey::Char('t')]);
+        let (saved, _) = Settings::load_from(&path);
+        assert_eq!(saved.theme, "retro");
+        assert_eq!(saved.rules, GameRules::default());
+
+        // Editing the rules saves them as played
+        let row = SETTINGS_ROWS
+            .iter()
+            .position(|r| *r == SettingsRow::PaddleHeight)
+            .unwrap();
+        app.push(Screen::Settings { selected: row });
+        press(&mut app, &s, &[MenuKey::Right, MenuKey::Back]);
+        let (saved, _) = Settings::load_from(&path);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(saved.rules, app.settings().rules);
+        assert_eq!(
+            saved.rules.paddle_height,
+            GameRules::ARCADE.paddle_height + 1
+        );
+    }
+
+    #[test]
+    fn user_themes_next_to_the_settings_join_the_cycle() {
+        let s = session();
+        let dir = std::env::temp_dir().join(format!("pong-user-theme-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(THEMES_DIR)).unwrap();
+        std::fs::write(dir.join(THEMES_DIR).join("amber.theme"), "paddle = X\n").unwrap();
+        let mut app = App::new(Settings::default(), Some(dir.join("settings.conf")));
+        s.set_theme(&Theme::builtin("rounded").unwrap());
+
+        app.push(Screen::Paused { selected: 0 });
+        press(&mut app, &s, &[MenuKey::Char('t')]);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(s.theme_name(), "amber");
+        assert_eq!(app.settings().theme, "amber");
+    }
+
+    #[test]
+    fn theme_override_is_shown_but_not_saved() {
+        let s = session();
+        let dir = std::env::temp_dir().join(format!("pong-theme-{}", std::process::id()));
+        let path = dir.join("settings.conf");
+        let mut app = App::new(Settings::default(), Some(path.clone()));
+        s.set_theme(&Theme::builtin("retro").unwrap());
+        app.override_theme(s.theme_name());
+        assert_eq!(app.settings_value(SettingsRow::Theme), "< retro >");
+
+        // Saving a key rebind keeps the saved theme
+        let row = SETTINGS_ROWS
+            .iter()
+            .position(|r| *r == SettingsRow::Key(Action::LeftUp))
+            .unwrap();
+        app.push(Screen::Settings { selected: row });
+        press(&mut app, &s, &[MenuKey::Select]);
+        app.capture_key(KeyCode::Char('i'));
+        press(&mut app, &s, &[MenuKey::Back]);
+        let (saved, _) = Settings::load_from(&path);
+        assert_eq!(saved.theme, "classic");
+        assert_eq!(saved.keys.left_up, KeyCode::Char('i'));
+
+        // Cycling from the row moves on from the theme on screen
+        app.push(Screen::Settings { selected: 0 });
+        press(&mut app, &s, &[MenuKey::Right, MenuKey::Back]);
+        let (saved, _) = Settings::load_from(&path);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(saved.theme, s.theme_name());
+        assert_eq!(app.settings().theme, s.theme_name());
+        assert_ne!(saved.theme, "retro");
+    }
+
+    #[test]
+    fn rules_row_cycles_presets() {
+        let s = session();
+        let mut app = App::default();
+        app.push(Screen::Settings { selected: 1 });
+        press(&mut app, &s, &[MenuKey::Right]);
+        assert_eq!(app.settings().rules, GameRules::FAST);
+        press(&mut app, &s, &[MenuKey::Left, MenuKey::Left]);
+        assert_eq!(app.settings().rules, GameRules::TOURNAMENT);
+
+        // Editing a rule makes them custom; the next preset starts the cycle again
+        press(&mut app, &s, &[MenuKey::Down, MenuKey::Right]);
+        assert_eq!(app.settings().rules.preset_name(), None);
+        press(&mut app, &s, &[MenuKey::Up, MenuKey::Right]);
+        assert_eq!(app.settings().rules, GameRules::CLASSIC);
+    }
+
+    #[test]
+    fn winning_shows_game_over_and_rematch_starts_fresh() {
+        let s = session();
+        let mut app = App::default();
+        app.handicaps[0].assist = 1;
+        app.start_match(GameMode::Classic, false);
+        app.game.as_mut().unwrap().board.score.right = app.settings.rules.points_to_win;
+        app.play_frame(InputState::new(), Duration::ZERO);
+        assert_eq!(app.screen(), Some(Screen::GameOver { selected: 0 }));
+
+        app.render(&s, &FrameStats::new()).unwrap();
+        let out = String::from_utf8(s.writer().clone()).unwrap();
+        assert!(out.contains("Player 2 wins! (right side)"));
+        assert!(out.contains("Paddle hits"));
+        assert!(out.contains("Handicap") && out.contains("A1"));
+        assert!(out.contains("Top edge"));
+        assert!(out.contains("Rematch (swap sides)"));
+
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.screen(), Some(Screen::InGame));
+        assert_eq!(app.game().unwrap().board.score, Default::default());
+        assert!(app.game().unwrap().swapped);
+        // Player 1's assist went with them to the right
+        assert_eq!(app.game().unwrap().board.right_handicap.assist, 1);
+    }
+
+    #[test]
+    fn quit_from_game_over_empties_the_stack() {
+        let s = session();
+        let mut app = App::default();
+        app.start_match(GameMode::Classic, false);
+        app.game.as_mut().unwrap().board.score.left = app.settings.rules.points_to_win;
+        app.play_frame(InputState::new(), Duration::ZERO);
+        press(
+            &mut app,
+            &s,
+            &[MenuKey::Down, MenuKey::Down, MenuKey::Select],
+        );
+        assert_eq!(app.screen(), None);
+    }
+
+    #[test]
+    fn sets_mode_reports_sets_on_game_over() {
+        let s = session();
+        let mut settings = Settings::default();
+        settings.rules.points_to_win = 1;
+        settings.rules.games_per_set = 1;
+        let mut app = App::new(settings, None);
+        press(
+            &mut app,
+            &s,
+            &[
+                MenuKey::Select,
+                MenuKey::Down,
+                MenuKey::Select,
+                MenuKey::Select,
+            ],
+        );
+        let game = app.game.as_mut().unwrap();
+        assert_eq!(game.board.sets.as_ref().unwrap().best_of, 3);
+        assert_eq!(game.board.clock, None);
+
+        // Straight sets; the set-by-set play is covered in game_loop
+        let sets = game.board.sets.as_mut().unwrap();
+        sets.win_game(Side::Left);
+        sets.win_game(Side::Left);
+        app.play_frame(InputState::new(), Duration::ZERO);
+        assert_eq!(app.screen(), Some(Screen::GameOver { selected: 0 }));
+
+        app.render(&s, &FrameStats::new()).unwrap();
+        let out = String::from_utf8(s.writer().clone()).unwrap();
+        assert!(out.contains("Player 1 wins!"));
+        assert!(out.contains("Sets 2 - 0   (1-0, 1-0)"));
+        let panel = app.menu_panel(Screen::GameOver { selected: 0 });
+        assert!(panel.render(&RenderStyle::ascii()).height() <= 24);
+    }
+
+    #[test]
+    fn timed_mode_starts_the_clock() {
+        let s = session();
+        let mut app = App::default();
+        let keys = [MenuKey::Down, MenuKey::Down, MenuKey::Down, MenuKey::Select];
+        press(&mut app, &s, &[MenuKey::Select]);
+        press(&mut app, &s, &keys);
+        press(&mut app, &s, &[MenuKey::Select]);
+        let game = app.game().unwrap();
+        assert_eq!(game.target_score, None);
+        let clock = game.board.clock.unwrap();
+        assert_eq!(
+            clock.remaining(game.board.rules.fps),
+            Duration::from_secs(180)
+        );
+    }
+
+    #[test]
+    fn unbindable_key_notice_fits_on_screen() {
+        let mut app = App::default();
+        let last_key_row = SETTINGS_ROWS.len() - 2;
+        app.push(Screen::Settings {
+            selected: last_key_row,
+        });
+        app.handle_key(MenuKey::Select, &session());
+        app.capture_key(KeyCode::Char(' '));
+        assert_eq!(app.settings().keys, Default::default());
+        let panel = app.menu_panel(Screen::Settings {
+            selected: last_key_row,
+        });
+        let fb = panel.render(&RenderStyle::ascii());
+        assert!(fb.to_string_lines().contains("can't be bound"));
+        assert!(fb.height() <= 24);
+    }
+
+    #[test]
+    fn squash_best_is_saved_next_to_the_settings() {
+        let s = session();
+        let dir = std::env::temp_dir().join(format!("pong-records-{}", std::process::id()));
+        let mut app = App::new(Settings::default(), Some(dir.join("settings.conf")));
+        press(
+            &mut app,
+            &s,
+            &[MenuKey::Select, MenuKey::Up, MenuKey::Up, MenuKey::Select],
+        );
+        press(&mut app, &s, &[MenuKey::Select]);
+        let game = app.game.as_mut().unwrap();
+        assert!(game.board.right_wall);
+
+        // A run of 4 returns, then leaving the match keeps it
+        game.board.score.left = 4;
+        game.board.score.right = 4;
+        app.play_frame(
+            InputState {
+                quit: true,
+                ..InputState::new()
+            },
+            Duration::ZERO,
+        );
+        let (saved, errors) = Records::load_from(&dir.join(RECORDS_FILE));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(saved.squash_best, 4);
+        assert_eq!(app.records().squash_best, 4);
+    }
+
+    #[test]
+    fn breakout_starts_with_bricks_and_keeps_no_record() {
+        let s = session();
+        let mut app = App::default();
+        press(
+            &mut app,
+            &s,
+            &[MenuKey::Select, MenuKey::Up, MenuKey::Select],
+        );
+        press(&mut app, &s, &[MenuKey::Select]);
+        assert_eq!(app.mode, GameMode::Breakout);
+        let game = app.game.as_mut().unwrap();
+        assert!(game.board.right_wall);
+        assert_eq!(game.board.breakout.as_ref().unwrap().level, 1);
+
+        // Misses are not a squash run
+        game.board.score.right = 9;
+        app.play_frame(
+            InputState {
+                quit: true,
+                ..InputState::new()
+            },
+            Duration::ZERO,
+        );
+        assert_eq!(app.records().squash_best, 0);
+    }
+}
@@ -0,0 +1,116 @@
+//! Command-line options.
+
+use std::path::PathBuf;
+
+/// Usage text printed for `--help` and on bad arguments.
+pub const USAGE: &str = "\
+Usage: pong_term [OPTIONS]
+
+Options:
+  --theme <NAME>   Render with a named theme (classic, retro, high-contrast,
+                   double, rounded, or a custom theme from the config directory)
+  --rules <RULES>  Play with a rules preset (classic, fast, arcade, tournament)
+                   or a rules file, for this run only
+  --record <FILE>  Record the session as an asciicast v2 file (asciinema play)
+  -h, --help       Show this help
+";
+
+/// Parsed command-line options.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliOptions {
+    pub theme: Option<String>,
+    /// Preset name or path of a rules file.
+    pub rules: Option<String>,
+    pub record: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl CliOptions {
+    /// Parse arguments, excluding the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut opts = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // Accept both `--opt value` and `--opt=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| format!("{name} needs a value"))
+            };
+
+            match flag.as_str() {
+                "--theme" => opts.theme = Some(value("--theme")?),
+                "--rules" => opts.rules = Some(value("--rules")?),
+                "--record" => opts.record = Some(PathBuf::from(value("--record")?)),
+                "-h" | "--help" => opts.help = true,
+                _ => return Err(format!("unknown option `{arg}`")),
+            }
+        }
+
+        Ok(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliOptions, String> {
+        CliOptions::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_theme_in_both_forms() {
+        assert_eq!(
+            parse(&["--theme", "retro"]).unwrap().theme.as_deref(),
+            Some("retro")
+        );
+        assert_eq!(
+            parse(&["--theme=double"]).unwrap().theme.as_deref(),
+            Some("double")
+        );
+        assert_eq!(parse(&[]).unwrap(), CliOptions::default());
+    }
+
+    #[test]
+    fn parses_record_path() {
+        assert_eq!(
+            parse(&["--record", "clip.cast"]).unwrap().record,
+            Some(PathBuf::from("clip.cast"))
+        );
+        assert_eq!(
+            parse(&["--record"]),
+            Err("--record needs a value".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(
+            parse(&["--rules=house.rules"]).unwrap().rules.as_deref(),
+            Some("house.rules")
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert_eq!(
+            parse(&["--theme"]),
+            Err("--theme needs a value".to_string())
+        );
+        assert_eq!(
+            parse(&["--fast"]),
+            Err("unknown option `--fast`".to_string())
+        );
+        assert!(parse(&["-h"]).unwrap().help);
+    }
+}
@@ -0,0 +1,119 @@
+//! Configuration files: locating the config directory and parsing the
+//! simple `key = value` text format used for themes.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Directory name under the XDG config home.
+pub const APP_DIR: &str = "pong_term";
+
+/// The game's config directory: `$XDG_CONFIG_HOME/pong_term`, falling back
+/// to `~/.config/pong_term`. None if neither variable is usable.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(xdg).join(APP_DIR));
+    }
+    std::env::var_os("HOME")
+        .filter(|v| !v.is_empty())
+        .map(|home| PathBuf::from(home).join(".config").join(APP_DIR))
+}
+
+/// A problem found while reading a config file.
+/// `line` is 1-based; 0 means the problem isn't tied to a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ConfigError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}: {}", self.line, self.message)
+        } else {
+            write!(f, "{}", self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// One `key = value` line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+}
+
+/// Parse `key = value` lines. Blank lines and lines starting with `#` are
+/// skipped; keys and values are trimmed. Malformed lines are reported and
+/// skipped so every problem in a file shows up at once.
+pub fn parse_entries(text: &str) -> (Vec<Entry>, Vec<ConfigError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match trimmed.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => entries.push(Entry {
+                line,
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            }),
+            _ => errors.push(ConfigError::new(line, "expected `key = value`")),
+        }
+    }
+
+    (entries, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries_and_skips_comments() {
+        let text = "# theme\n\nball = ●\n  color.ball = #ffcc00  \nnet=:\n";
+        let (entries, errors) = parse_entries(text);
+        assert!(errors.is_empty());
+        let pairs: Vec<(usize, &str, &str)> = entries
+            .iter()
+            .map(|e| (e.line, e.key.as_str(), e.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (3, "ball", "●"),
+                (4, "color.ball", "#ffcc00"),
+                (5, "net", ":")
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let (entries, errors) = parse_entries("paddle\n= x\nball = o\n");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            vec![
+                "line 1: expected `key = value`",
+                "line 2: expected `key = value`"
+            ]
+        );
+    }
+}
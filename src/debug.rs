@@ -0,0 +1,160 @@
+//! Debug overlay for tuning: frame timing and physics internals.
+//! Everything here only reads game state, so toggling it never changes
+//! the simulation.
+
+use crate::framebuffer::{FrameBuffer, Layers};
+use crate::model::{BallEvent, Board, Paddle, PaddleHitLocation};
+use crate::terminal::RenderStyle;
+use crate::widgets::{Panel, Widget};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Frames kept for the rolling statistics.
+pub const STATS_WINDOW: usize = 60;
+
+/// Rolling frame timing: work time per frame and the interval between frames.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    samples: VecDeque<(Duration, Duration)>,
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one frame: `work` is the time spent before sleeping, `interval`
+    /// the time since the previous frame started.
+    pub fn record(&mut self, work: Duration, interval: Duration) {
+        if self.samples.len() == STATS_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((work, interval));
+    }
+
+    /// Measured frames per second over the window; 0 before any frame.
+    pub fn fps(&self) -> f64 {
+        let total: Duration = self.samples.iter().map(|(_, interval)| *interval).sum();
+        if total.is_zero() {
+            0.0
+        } else {
+            self.samples.len() as f64 / total.as_secs_f64()
+        }
+    }
+
+    /// Minimum, average and maximum work time over the window.
+    pub fn work_times(&self) -> Option<(Duration, Duration, Duration)> {
+        let times = self.samples.iter().map(|(work, _)| *work);
+        let min = times.clone().min()?;
+        let max = times.clone().max()?;
+        let avg = times.sum::<Duration>() / self.samples.len() as u32;
+        Some((min, avg, max))
+    }
+}
+
+/// Marker for each hit zone: the way the ball leaves the paddle.
+fn zone_glyph(location: PaddleHitLocation) -> char {
+    match location {
+        PaddleHitLocation::TopEdge => '^',
+        PaddleHitLocation::TopMid => '/',
+        PaddleHitLocation::Center => '-',
+        PaddleHitLocation::BottomMid => '\\',
+        PaddleHitLocation::BottomEdge => 'v',
+    }
+}
+
+fn draw_hit_zones(fb: &mut FrameBuffer, paddle: &Paddle) {
+    for y in paddle.y..paddle.y + paddle.height {
+        fb.set(paddle.x, y, zone_glyph(paddle.hit_location(y)));
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Overlay text: timing and physics values.
+fn debug_panel(board: &Board, stats: &FrameStats, last_event: BallEvent) -> Panel {
+    let work = match stats.work_times() {
+        Some((min, avg, max)) => {
+            format!("{:.2}/{:.2}/{:.2}", millis(min), millis(avg), millis(max))
+        }
+        None => "-".to_string(),
+    };
+    let ball = &board.ball;
+    Panel::new("DEBUG")
+        .line(format!("FPS      {:.1}", stats.fps()))
+        .line(format!("Work ms  {work}"))
+        .line(format!("Ball     ({}, {})", ball.x, ball.y))
+        .line(format!("Velocity ({:+}, {:+})", ball.dx, ball.dy))
+        .line(format!("Frame    {}", board.frame_counter))
+        .line(format!("Event    {last_event:?}"))
+}
+
+/// Draw the debug overlay onto a composed frame: hit zones on both paddles
+/// and a stats panel in the top-left corner of the court.
+pub fn draw_debug_overlay(
+    fb: &mut FrameBuffer,
+    board: &Board,
+    stats: &FrameStats,
+    last_event: BallEvent,
+    style: &RenderStyle,
+) {
+    draw_hit_zones(fb, &board.left);
+    draw_hit_zones(fb, &board.right);
+
+    // Just inside the border, clear of the left paddle column
+    let panel = debug_panel(board, stats, last_event).render(style);
+    let mut layers = Layers::new();
+    layers.push(1, board.left.x + 2, 1, panel);
+    layers.compose_onto(fb);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_roll_over_the_window() {
+        let mut stats = FrameStats::new();
+        assert_eq!(stats.fps(), 0.0);
+        assert_eq!(stats.work_times(), None);
+
+        stats.record(Duration::from_millis(9), Duration::from_millis(20));
+        for _ in 0..STATS_WINDOW {
+            stats.record(Duration::from_millis(2), Duration::from_millis(10));
+        }
+        // The slow first frame has left the window
+        assert!((stats.fps() - 100.0).abs() < 1e-9);
+        let two = Duration::from_millis(2);
+        assert_eq!(stats.work_times(), Some((two, two, two)));
+
+        stats.record(Duration::from_millis(8), Duration::from_millis(10));
+        let (min, _, max) = stats.work_times().unwrap();
+        assert_eq!((min, max), (two, Duration::from_millis(8)));
+    }
+
+    #[test]
+    fn overlay_marks_hit_zones_and_shows_physics() {
+        let board = Board::new_static();
+        let style = RenderStyle::ascii();
+        let mut fb = FrameBuffer::new(board.width, board.height, ' ');
+        crate::draw::draw_board(&mut fb, &board, &style);
+        draw_debug_overlay(
+            &mut fb,
+            &board,
+            &FrameStats::new(),
+            BallEvent::WallBounce,
+            &style,
+        );
+
+        let column: String = (board.right.y..board.right.y + board.right.height)
+            .map(|y| fb.get(board.right.x, y).unwrap())
+            .collect();
+        assert_eq!(column, "^/-\\v");
+
+        let text = fb.to_string_lines();
+        assert!(text.contains(&format!("Ball     ({}, {})", board.ball.x, board.ball.y)));
+        assert!(text.contains("Event    WallBounce"));
+    }
+}
@@ -1,7 +1,9 @@
 //! Drawing helpers that render model entities into a FrameBuffer.
 //! Pure functions; no ANSI or I/O concerns here.
 
-use crate::framebuffer::FrameBuffer;
+use crate::effects::Effects;
+use crate::framebuffer::{Element, FrameBuffer};
+use crate::hud::{self, Hud, DIGIT_HEIGHT, HUD_HEIGHT};
 use crate::model::{Ball, Board, Paddle};
 use crate::terminal::RenderStyle;
 use crate::util::{char_width, str_width};
@@ -16,23 +18,50 @@ pub fn draw_border(fb: &mut FrameBuffer, style: &RenderStyle) {
     }
 
     // Top row
-    fb.set(0, 0, style.border_corner_tl);
+    fb.set_as(0, 0, style.border_corner_tl, Element::Border);
     for x in 1..w - 1 {
-        fb.set(x, 0, style.border_horizontal);
+        fb.set_as(x, 0, style.border_horizontal, Element::Border);
     }
-    fb.set(w - 1, 0, style.border_corner_tr);
+    fb.set_as(w - 1, 0, style.border_corner_tr, Element::Border);
 
     // Bottom row
-    fb.set(0, h - 1, style.border_corner_bl);
+    fb.set_as(0, h - 1, style.border_corner_bl, Element::Border);
     for x in 1..w - 1 {
-        fb.set(x, h - 1, style.border_horizontal);
+        fb.set_as(x, h - 1, style.border_horizontal, Element::Border);
     }
-    fb.set(w - 1, h - 1, style.border_corner_br);
+    fb.set_as(w - 1, h - 1, style.border_corner_br, Element::Border);
 
     // Left and right columns
     for y in 1..h - 1 {
-        fb.set(0, y, style.border_vertical);
-        fb.set(w - 1, y, style.border_vertical);
+        fb.set_as(0, y, style.border_vertical, Element::Border);
+        fb.set_as(w - 1, y, style.border_vertical, Element::Border);
+    }
+}
+
+/// Draw court markings: dashed center net and optional service line.
+/// Background layer; paddles and ball draw over it.
+pub fn draw_court(fb: &mut FrameBuffer, style: &RenderStyle) {
+    let w = fb.width();
+    let h = fb.height();
+
+    if w < 3 || h < 3 {
+        return;
+    }
+
+    // Net dashes on every other interior row
+    let center = w / 2;
+    for y in (1..h - 1).step_by(2) {
+        fb.set_as(center, y, style.net, Element::Court);
+    }
+
+    // Service line across the middle half of the court, broken at the net
+    if let Some(mark) = style.service_mark {
+        let y = h / 2;
+        for x in (w / 4..=w * 3 / 4).filter(|&x| x != center) {
+            if x > 0 && x < w - 1 {
+                fb.set_as(x, y, mark, Element::Court);
+            }
+        }
     }
 }
 
@@ -54,7 +83,7 @@ pub fn draw_paddle(fb: &mut FrameBuffer, p: &Paddle, style: &RenderStyle) {
     let end_y = p.y.saturating_add(p.height).min(h.saturating_sub(1));
     for y in start_y..end_y {
         if y > 0 && y < h - 1 {
-            fb.set(x, y, style.paddle);
+            fb.set_as(x, y, style.paddle, Element::Paddle);
         }
     }
 }
@@ -70,7 +99,137 @@ pub fn draw_ball(fb: &mut FrameBuffer, b: &Ball, style: &RenderStyle) {
     let x = b.x.min(w.saturating_sub(2));
     let y = b.y.min(h.saturating_sub(2));
     if x > 0 && x < w - 1 && y > 0 && y < h - 1 {
-        fb.set(x, y, style.ball);
+        fb.set_as(x, y, style.ball, Element::Ball);
+    }
+}
+
+/// Braille dot columns per terminal cell.
+pub const BRAILLE_DOTS_X: usize = 2;
+/// Braille dot rows per terminal cell.
+pub const BRAILLE_DOTS_Y: usize = 4;
+
+const BRAILLE_BASE: u32 = 0x2800;
+
+/// Bit for the dot at (dx, dy) inside a braille cell (Unicode dot numbering).
+fn braille_bit(dx: usize, dy: usize) -> u32 {
+    match (dx, dy) {
+        (0, 0) => 0x01,
+        (0, 1) => 0x02,
+        (0, 2) => 0x04,
+        (1, 0) => 0x08,
+        (1, 1) => 0x10,
+        (1, 2) => 0x20,
+        (0, 3) => 0x40,
+        (1, 3) => 0x80,
+        _ => 0,
+    }
+}
+
+/// Set one braille dot, merging with any braille pattern already in the cell.
+fn set_braille_dot(fb: &mut FrameBuffer, dot_x: usize, dot_y: usize) {
+    let x = dot_x / BRAILLE_DOTS_X;
+    let y = dot_y / BRAILLE_DOTS_Y;
+    let bit = braille_bit(dot_x % BRAILLE_DOTS_X, dot_y % BRAILLE_DOTS_Y);
+
+    // Keep dots already drawn in this cell; anything else gets replaced
+    let existing = match fb.get(x, y) {
+        Some(ch) if (BRAILLE_BASE..=BRAILLE_BASE + 0xFF).contains(&(ch as u32)) => {
+            ch as u32 - BRAILLE_BASE
+        }
+        Some(_) => 0,
+        None => return,
+    };
+    if let Some(ch) = char::from_u32(BRAILLE_BASE + (existing | bit)) {
+        fb.set_as(x, y, ch, Element::Ball);
+    }
+}
+
+/// Ball position in braille dot coordinates (top-left of its 2×2 dot blob).
+/// Between moves the blob glides from the ball's cell towards the next one
+/// by the share of frames elapsed, so the ball doesn't jump a whole cell at a
+/// time. It waits in its cell when the next one holds a paddle or a brick, so
+/// it never covers them before the bounce.
+pub fn ball_dot_position(board: &Board) -> (usize, usize) {
+    let b = &board.ball;
+    let (elapsed, gap) = ball_move_progress(board);
+    let (next_x, next_y) = (b.x as i64 + b.dx as i64, b.y as i64 + b.dy as i64);
+    let paddle_ahead =
+        next_x == board.left.x as i64 || (!board.right_wall && next_x == board.right.x as i64);
+    let brick_ahead = board.breakout.as_ref().is_some_and(|breakout| {
+        breakout.bricks.iter().any(|brick| {
+            [(next_x, b.y as i64), (b.x as i64, next_y), (next_x, next_y)]
+                .iter()
+                .any(|&(x, y)| x >= 0 && y >= 0 && brick.contains(x as usize, y as usize))
+        })
+    });
+    let (elapsed, gap) = if paddle_ahead || brick_ahead {
+        (0, 1)
+    } else {
+        (elapsed as i64, gap as i64)
+    };
+
+    // Blob sits in the vertical middle of its cell (dot rows 1-2)
+    let base_x = (b.x * BRAILLE_DOTS_X) as i64;
+    let base_y = (b.y * BRAILLE_DOTS_Y + 1) as i64;
+    let off_x = b.dx as i64 * elapsed * BRAILLE_DOTS_X as i64 / gap;
+    let off_y = b.dy as i64 * elapsed * BRAILLE_DOTS_Y as i64 / gap;
+
+    // Clamp inside the border so the blob never touches it
+    let max_x = (board.width.saturating_sub(1) * BRAILLE_DOTS_X).saturating_sub(2) as i64;
+    let max_y = (board.height.saturating_sub(1) * BRAILLE_DOTS_Y).saturating_sub(2) as i64;
+    let x = (base_x + off_x).clamp(BRAILLE_DOTS_X as i64, max_x.max(BRAILLE_DOTS_X as i64));
+    let y = (base_y + off_y).clamp(BRAILLE_DOTS_Y as i64, max_y.max(BRAILLE_DOTS_Y as i64));
+    (x as usize, y as usize)
+}
+
+/// Frames since the ball last moved, and from that move to the next one.
+/// Follows `Board::update_ball`: the ball moves when the frame counter
+/// reaches a multiple of the speed divisor, except that an angled ball skips
+/// the frames that would land on a multiple of four.
+fn ball_move_progress(board: &Board) -> (usize, usize) {
+    let divisor = board.rules.ball_speed_divisor.max(1);
+    let angled = board.ball.dy != 0;
+    let moves_at = |c: usize| c % divisor == 0 && !(angled && c % 4 == 0);
+    let counter = board.frame_counter;
+    let window = 4 * divisor;
+
+    // A reset counter means the ball was just placed
+    let last = (counter.saturating_sub(window).max(1)..=counter)
+        .rev()
+        .find(|&c| moves_at(c))
+        .unwrap_or(if counter < window { 0 } else { counter });
+    let next = (counter + 1..=counter + window)
+        .find(|&c| moves_at(c))
+        .unwrap_or(counter + 1);
+    (counter - last, next - last)
+}
+
+/// Draw the ball as a 2×2 braille dot blob at a sub-cell position.
+/// Falls back to the style's ball glyph when braille is not enabled.
+pub fn draw_ball_subcell(fb: &mut FrameBuffer, dot_x: usize, dot_y: usize, style: &RenderStyle) {
+    let w = fb.width();
+    let h = fb.height();
+
+    if w < 3 || h < 3 {
+        return;
+    }
+    if !style.smooth_ball {
+        let x = dot_x / BRAILLE_DOTS_X;
+        let y = dot_y / BRAILLE_DOTS_Y;
+        if x > 0 && x < w - 1 && y > 0 && y < h - 1 {
+            fb.set_as(x, y, style.ball, Element::Ball);
+        }
+        return;
+    }
+
+    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        let x = dot_x + dx;
+        let y = dot_y + dy;
+        let cx = x / BRAILLE_DOTS_X;
+        let cy = y / BRAILLE_DOTS_Y;
+        if cx > 0 && cx < w - 1 && cy > 0 && cy < h - 1 {
+            set_braille_dot(fb, x, y);
+        }
     }
 }
 
@@ -102,13 +261,270 @@ pub fn draw_centered_text(fb: &mut FrameBuffer, text: &str, row: usize) {
     }
 }
 
+/// Columns between the center line and each player's score.
+const HUD_CENTER_GAP: usize = 3;
+
+/// Set a HUD cell, but only inside the strip above the court.
+fn hud_set(fb: &mut FrameBuffer, x: usize, y: usize, ch: char) {
+    // Wide glyphs need their second column on screen too
+    if x + char_width(ch).max(1) <= fb.width() && y < HUD_HEIGHT {
+        fb.set(x, y, ch);
+    }
+}
+
+/// Write HUD text starting at column `x` (accounts for Unicode display width).
+fn hud_text(fb: &mut FrameBuffer, mut x: usize, y: usize, text: &str) {
+    for ch in text.chars() {
+        hud_set(fb, x, y, ch);
+        x += char_width(ch);
+    }
+}
+
+/// Draw `value` in big block digits with its top-left corner at (x, y).
+fn draw_big_number(fb: &mut FrameBuffer, value: u32, x: usize, y: usize, block: char) {
+    for (dx, dy) in hud::big_number_cells(value) {
+        hud_set(fb, x + dx, y + dy, block);
+    }
+}
+
+/// Draw the scoreboard into the top `HUD_HEIGHT` rows of the screen, the
+/// strip above the court, so it never covers the paddles or the ball's path.
+pub fn draw_hud(fb: &mut FrameBuffer, board: &Board, hud: &Hud, style: &RenderStyle) {
+    if fb.height() < HUD_HEIGHT {
+        return;
+    }
+    let center = fb.width() / 2;
+    let left = board.score.left;
+    let right = board.score.right;
+
+    // Big digits in Unicode, a single row of plain digits otherwise
+    let (left_w, right_w) = match style.score_block {
+        Some(_) => (hud::big_number_width(left), hud::big_number_width(right)),
+        None => (left.to_string().len(), right.to_string().len()),
+    };
+    let left_x = center.saturating_sub(HUD_CENTER_GAP + left_w);
+    let right_x = center + HUD_CENTER_GAP;
+
+    match style.score_block {
+        Some(block) => {
+            draw_big_number(fb, left, left_x, 0, block);
+            draw_big_number(fb, right, right_x, 0, block);
+        }
+        None => {
+            hud_text(fb, left_x, 0, &left.to_string());
+            hud_text(fb, right_x, 0, &right.to_string());
+        }
+    }
+
+    // Names flank the scores on their first row
+    if let Some(name) = &hud.left_name {
+        let x = left_x.saturating_sub(2 + str_width(name));
+        hud_text(fb, x, 0, name);
+    }
+    if let Some(name) = &hud.right_name {
+        hud_text(fb, right_x + right_w + 2, 0, name);
+    }
+
+    // Status and rally share the row under the digits
+    let mut line = hud.status.clone().unwrap_or_default();
+    if hud.show_rally && board.rally > 0 {
+        if !line.is_empty() {
+            line.push_str("   ");
+        }
+        line.push_str(&format!("Rally {}", board.rally));
+    }
+    let x = center.saturating_sub(str_width(&line) / 2);
+    hud_text(fb, x, HUD_HEIGHT - 1, &line);
+}
+
+/// The whole screen: the HUD strip on top and the drawn `court` below it.
+pub fn compose_screen(
+    court: &FrameBuffer,
+    board: &Board,
+    hud: &Hud,
+    style: &RenderStyle,
+) -> FrameBuffer {
+    let mut fb = FrameBuffer::new(court.width(), HUD_HEIGHT + court.height(), ' ');
+    draw_hud(&mut fb, board, hud, style);
+    fb.blit(court, 0, HUD_HEIGHT);
+    fb
+}
+
+/// Draw the squash back wall: a paddle spanning the court in the right
+/// paddle's column.
+pub fn draw_back_wall(fb: &mut FrameBuffer, board: &Board, style: &RenderStyle) {
+    let wall = Paddle {
+        x: board.right.x,
+        y: 1,
+        height: board.height.saturating_sub(2),
+        ..board.right.clone()
+    };
+    draw_paddle(fb, &wall, style);
+}
+
+/// Draw the breakout bricks that are still standing, clipped to the court.
+pub fn draw_bricks(fb: &mut FrameBuffer, board: &Board, style: &RenderStyle) {
+    let Some(breakout) = &board.breakout else {
+        return;
+    };
+    let w = fb.width();
+    let h = fb.height();
+    for brick in &breakout.bricks {
+        for y in brick.y..brick.y + brick.height {
+            for x in brick.x..brick.x + brick.width {
+                if x > 0 && x < w.saturating_sub(1) && y > 0 && y < h.saturating_sub(1) {
+                    fb.set_as(x, y, style.brick, Element::Brick);
+                }
+            }
+        }
+    }
+}
+
+/// Draw a boxed banner of centered lines with its top edge at `top`.
+/// Everything inside the box is blanked so the court doesn't show through.
+pub fn draw_banner(fb: &mut FrameBuffer, lines: &[&str], top: usize, style: &RenderStyle) {
+    let w = fb.width();
+    let text_w = lines.iter().map(|l| str_width(l)).max().unwrap_or(0);
+    let box_w = text_w + 4;
+    let box_h = lines.len() + 2;
+    if box_w + 2 > w || top + box_h > fb.height() {
+        return;
+    }
+    let left = (w - box_w) / 2;
+    let right = left + box_w - 1;
+    let bottom = top + box_h - 1;
+
+    for y in top..=bottom {
+        for x in left..=right {
+            let ch = match (y == top || y == bottom, x == left || x == right) {
+                (true, true) => match (y == top, x == left) {
+                    (true, true) => style.border_corner_tl,
+                    (true, false) => style.border_corner_tr,
+                    (false, true) => style.border_corner_bl,
+                    (false, false) => style.border_corner_br,
+                },
+                (true, false) => style.border_horizontal,
+                (false, true) => style.border_vertical,
+                (false, false) => ' ',
+            };
+            let element = if ch == ' ' {
+                Element::Text
+            } else {
+                Element::Border
+            };
+            fb.set_as(x, y, ch, element);
+        }
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let mut x = left + 2 + (text_w - str_width(line)) / 2;
+        for ch in line.chars() {
+            fb.set(x, top + 1 + i, ch);
+            x += char_width(ch);
+        }
+    }
+}
+
+/// Draw the "GOAL!" banner for the player who just scored.
+pub fn draw_goal_banner(fb: &mut FrameBuffer, left_scored: bool, style: &RenderStyle) {
+    let scorer = if left_scored {
+        "Left player scores"
+    } else {
+        "Right player scores"
+    };
+    let top = (fb.height() / 2).saturating_sub(5);
+    draw_banner(fb, &["GOAL!", scorer], top, style);
+}
+
+/// Draw a serve countdown number above the center of the court.
+pub fn draw_countdown(fb: &mut FrameBuffer, count: u32, style: &RenderStyle) {
+    let center = fb.width() / 2;
+    let mid = fb.height() / 2;
+    match style.score_block {
+        Some(block) => {
+            let digit_w = hud::big_number_width(count);
+            let x = center.saturating_sub(digit_w / 2);
+            let y = mid.saturating_sub(DIGIT_HEIGHT + 1);
+            // Clear a one-cell margin so the net doesn't cut through the digit
+            for cy in y.saturating_sub(1)..y + DIGIT_HEIGHT + 1 {
+                for cx in x.saturating_sub(1)..x + digit_w + 1 {
+                    if cx > 0 && cx < fb.width() - 1 && cy > 0 && cy < fb.height() - 1 {
+                        fb.set(cx, cy, ' ');
+                    }
+                }
+            }
+            for (dx, dy) in hud::big_number_cells(count) {
+                fb.set(x + dx, y + dy, block);
+            }
+        }
+        None => {
+            let text = format!(" {count} ");
+            let x = center.saturating_sub(str_width(&text) / 2);
+            for (i, ch) in text.chars().enumerate() {
+                fb.set(x + i, mid.saturating_sub(2), ch);
+            }
+        }
+    }
+}
+
 /// Draw a complete static board into the framebuffer with given style.
 /// Provided for convenience where a fully-rendered frame is desired.
 pub fn draw_board(fb: &mut FrameBuffer, board: &Board, style: &RenderStyle) {
     draw_border(fb, style);
+    draw_court(fb, style);
+    draw_bricks(fb, board, style);
     draw_paddle(fb, &board.left, style);
-    draw_paddle(fb, &board.right, style);
-    draw_ball(fb, &board.ball, style);
+    if board.right_wall {
+        draw_back_wall(fb, board, style);
+    } else {
+        draw_paddle(fb, &board.right, style);
+    }
+    if style.smooth_ball {
+        let (dot_x, dot_y) = ball_dot_position(board);
+        draw_ball_subcell(fb, dot_x, dot_y, style);
+    } else {
+        draw_ball(fb, &board.ball, style);
+    }
+}
+
+/// Draw transient effects on top of a finished board frame.
+/// The trail only fills empty cells so it never hides the ball or paddles.
+pub fn draw_effects(fb: &mut FrameBuffer, board: &Board, effects: &Effects, style: &RenderStyle) {
+    let w = fb.width();
+    let h = fb.height();
+
+    if w < 3 || h < 3 {
+        return;
+    }
+
+    // Ball trail, fading with age
+    let fade = style.trail.len();
+    for (age, (x, y)) in effects.trail().enumerate() {
+        let glyph = style.trail[(age * fade / crate::effects::TRAIL_LENGTH).min(fade - 1)];
+        if x > 0 && x < w - 1 && y > 0 && y < h - 1 && fb.get(x, y) == Some(' ') {
+            fb.set_as(x, y, glyph, Element::Ball);
+        }
+    }
+
+    // Paddle flash replaces the paddle glyph on the paddle that was hit
+    if let Some(flash) = effects.flash() {
+        let paddle = if flash.left {
+            &board.left
+        } else {
+            &board.right
+        };
+        for y in paddle.y..paddle.y + paddle.height {
+            if fb.get(paddle.x, y) == Some(style.paddle) {
+                fb.set_as(paddle.x, y, style.paddle_flash, Element::Paddle);
+            }
+        }
+    }
+
+    // Wall sparks sit on the border row itself
+    for spark in effects.sparks() {
+        if spark.x > 0 && spark.x < w - 1 {
+            fb.set_as(spark.x, spark.y, style.spark, Element::Ball);
+        }
+    }
 }
 
 /// Draw board with a message inside
@@ -127,10 +543,11 @@ pub fn draw_board_with_message(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::Board;
+    use crate::model::{Board, Breakout};
     use crate::terminal::RenderStyle;
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn border_has_expected_corners_and_edges() {
         let b = Board::new_static();
         let mut fb = FrameBuffer::new(b.width, b.height, ' ');
@@ -165,6 +582,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn paddles_and_ball_match_board() {
         let b = Board::new_static();
         let mut fb = FrameBuffer::new(b.width, b.height, ' ');
@@ -190,4 +608,321 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn net_is_dashed_down_the_center() {
+        let b = Board::new_static();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_court(&mut fb, &RenderStyle::ascii());
+        let center = b.width / 2;
+        for y in 1..(b.height - 1) {
+            let expected = if y % 2 == 1 { ':' } else { ' ' };
+            assert_eq!(fb.get(center, y), Some(expected));
+        }
+        // Border rows stay free for draw_border
+        assert_eq!(fb.get(center, 0), Some(' '));
+        assert_eq!(fb.get(center, b.height - 1), Some(' '));
+    }
+
+    #[test]
+    fn service_marks_are_optional() {
+        let b = Board::new_static();
+        let mut style = RenderStyle::unicode();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_court(&mut fb, &style);
+        assert_eq!(fb.get(b.width / 4, b.height / 2), Some(' '));
+
+        style.service_mark = Some('┈');
+        draw_court(&mut fb, &style);
+        assert_eq!(fb.get(b.width / 4, b.height / 2), Some('┈'));
+        assert_eq!(fb.get(b.width * 3 / 4, b.height / 2), Some('┈'));
+        assert_eq!(fb.get(b.width / 4 - 1, b.height / 2), Some(' '));
+    }
+
+    #[test]
+    fn paddles_and_ball_draw_over_court_markings() {
+        let mut b = Board::new_static();
+        b.left.x = b.width / 2; // put a paddle right on the net
+        b.left.y = 1;
+        b.ball.x = b.width / 2;
+        b.ball.y = b.height / 2 + 1; // odd row carries a net dash
+        let mut style = RenderStyle::ascii();
+        style.service_mark = Some('-');
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &style);
+        let s = fb.to_string_lines();
+        let grid: Vec<Vec<char>> = s.lines().map(|ln| ln.chars().collect()).collect();
+
+        // ball
+        assert_eq!(grid[b.ball.y][b.ball.x], 'o');
+
+        // left paddle, sitting on the net
+        for (y, row) in grid.iter().enumerate().skip(b.left.y).take(b.left.height) {
+            if y > 0 && y < b.height - 1 {
+                assert_eq!(row[b.left.x], '|');
+            }
+        }
+
+        // right paddle crosses the service line
+        b.right.x = b.width * 3 / 4;
+        b.right.y = b.height / 2 - 2;
+        draw_board(&mut fb, &b, &style);
+        assert_eq!(fb.get(b.right.x, b.height / 2), Some('|'));
+
+        // net still visible below the paddle
+        assert_eq!(fb.get(b.width / 2, b.left.y + b.left.height + 1), Some(':'));
+    }
+
+    #[test]
+    fn goal_banner_is_boxed_and_names_scorer() {
+        let b = Board::new_static();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &RenderStyle::ascii());
+        draw_goal_banner(&mut fb, false, &RenderStyle::ascii());
+        let s = fb.to_string_lines();
+        let lines: Vec<&str> = s.lines().collect();
+        let top = b.height / 2 - 5;
+        assert!(lines[top].contains("+---------------------+"));
+        assert!(lines[top + 1].contains("|        GOAL!        |"));
+        assert!(lines[top + 2].contains("| Right player scores |"));
+        assert!(lines[top + 3].contains("+---------------------+"));
+    }
+
+    #[test]
+    fn countdown_draws_over_the_net() {
+        let b = Board::new_static();
+        let style = RenderStyle::unicode();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &style);
+        draw_countdown(&mut fb, 1, &style);
+
+        // "1" is centered: its stem runs down the net column
+        let center = b.width / 2;
+        let top = b.height / 2 - DIGIT_HEIGHT - 1;
+        for y in top..top + DIGIT_HEIGHT {
+            assert_eq!(fb.get(center, y), Some('█'));
+        }
+        // Margin row above the digit is cleared of net dashes
+        assert_eq!(fb.get(center, top - 1), Some(' '));
+    }
+
+    #[test]
+    fn braille_dot_patterns_for_subcell_positions() {
+        let mut style = RenderStyle::unicode();
+        style.smooth_ball = true;
+
+        // Blob aligned in the middle of one cell: dots 2,3,5,6
+        let mut fb = FrameBuffer::new(6, 4, ' ');
+        draw_ball_subcell(&mut fb, 2 * BRAILLE_DOTS_X, BRAILLE_DOTS_Y + 1, &style);
+        assert_eq!(fb.get(2, 1), Some('\u{2836}'));
+        assert_eq!(fb.get(3, 1), Some(' '));
+
+        // Half a cell to the right: right column here, left column next door
+        let mut fb = FrameBuffer::new(6, 4, ' ');
+        draw_ball_subcell(&mut fb, 2 * BRAILLE_DOTS_X + 1, BRAILLE_DOTS_Y + 1, &style);
+        assert_eq!(fb.get(2, 1), Some('\u{2830}'));
+        assert_eq!(fb.get(3, 1), Some('\u{2806}'));
+
+        // Straddling a row boundary: bottom dots above, top dots below
+        let mut fb = FrameBuffer::new(6, 4, ' ');
+        draw_ball_subcell(&mut fb, 2 * BRAILLE_DOTS_X, 2 * BRAILLE_DOTS_Y - 1, &style);
+        assert_eq!(fb.get(2, 1), Some('\u{28C0}'));
+        assert_eq!(fb.get(2, 2), Some('\u{2809}'));
+    }
+
+    #[test]
+    fn subcell_ball_falls_back_to_glyph_in_ascii() {
+        let style = RenderStyle::ascii();
+        let mut fb = FrameBuffer::new(6, 4, ' ');
+        draw_ball_subcell(&mut fb, 2 * BRAILLE_DOTS_X + 1, BRAILLE_DOTS_Y + 1, &style);
+        assert_eq!(fb.get(2, 1), Some('o'));
+        assert_eq!(fb.get(3, 1), Some(' '));
+    }
+
+    #[test]
+    fn ball_dot_position_interpolates_between_cells() {
+        let mut b = Board::new_static();
+        b.ball.x = 10;
+        b.ball.y = 10;
+        b.ball.dx = 1;
+        b.ball.dy = -1;
+
+        b.frame_counter = 0; // just moved
+        assert_eq!(ball_dot_position(&b), (20, 41));
+
+        b.frame_counter = 1; // halfway to the next cell
+        assert_eq!(ball_dot_position(&b), (21, 39));
+    }
+
+    #[test]
+    fn ball_dot_position_glides_through_angled_skip_frames() {
+        let mut b = Board::new_static();
+        b.ball.x = 10;
+        b.ball.y = 10;
+        b.ball.dx = 1;
+        b.ball.dy = 1;
+
+        // Moves land on frames 2 and 6; frame 4 is skipped, not a move
+        let xs: Vec<usize> = (2..6)
+            .map(|frame| {
+                b.frame_counter = frame;
+                ball_dot_position(&b).0
+            })
+            .collect();
+        assert!(xs.windows(2).all(|w| w[0] <= w[1]), "{xs:?}");
+        assert!(xs.iter().all(|&x| x / BRAILLE_DOTS_X <= b.ball.x + 1));
+    }
+
+    #[test]
+    fn ball_dot_position_stays_out_of_the_paddle_column() {
+        let mut b = Board::new_static();
+        b.ball.x = b.right.x - 1;
+        b.ball.y = b.right.y;
+        b.ball.dx = 1;
+        b.ball.dy = 0;
+        b.frame_counter = 1;
+        assert_eq!(ball_dot_position(&b).0 / BRAILLE_DOTS_X, b.ball.x);
+
+        b.ball.x = b.left.x + 1;
+        b.ball.dx = -1;
+        assert_eq!(ball_dot_position(&b).0 / BRAILLE_DOTS_X, b.ball.x);
+    }
+
+    #[test]
+    fn effects_draw_after_board_without_hiding_it() {
+        let mut b = Board::new_static();
+        let style = RenderStyle::ascii();
+        let mut fx = Effects::new();
+
+        // Trail from the left, ending under the ball's current position
+        for x in (b.ball.x - 3)..=b.ball.x {
+            b.ball.x = x;
+            fx.update(&b, crate::model::BallEvent::None);
+        }
+        fx.update(&b, crate::model::BallEvent::WallBounce);
+
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &style);
+        draw_effects(&mut fb, &b, &fx, &style);
+
+        assert_eq!(fb.get(b.ball.x, b.ball.y), Some('o'));
+        assert_eq!(fb.get(b.ball.x - 1, b.ball.y), Some('.'));
+        assert_eq!(fb.get(b.ball.x - 3, b.ball.y), Some('.'));
+        assert_eq!(fb.get(b.ball.x - 4, b.ball.y), Some(' '));
+        assert_eq!(fb.get(b.ball.x, 0), Some('*'));
+    }
+
+    #[test]
+    fn hud_draws_big_digits_and_names() {
+        let mut b = Board::new_static();
+        b.score.left = 1;
+        b.score.right = 10;
+        let hud = Hud::new().with_names("ANN", "BOB");
+        let court = FrameBuffer::new(b.width, b.height, ' ');
+        let fb = compose_screen(&court, &b, &hud, &RenderStyle::unicode());
+        let grid: Vec<Vec<char>> = fb
+            .to_string_lines()
+            .lines()
+            .map(|ln| ln.chars().collect())
+            .collect();
+
+        // "1" ends three columns left of center, "10" starts three right of it
+        let center = b.width / 2;
+        let row: String = grid[0][center - 6..center + 10].iter().collect();
+        assert_eq!(row, " █        █  ███");
+        let row: String = grid[4][center - 6..center + 10].iter().collect();
+        assert_eq!(row, "███      ███ ███");
+        assert_eq!(fb.element(center - 5, 0), Some(Element::Text));
+
+        let names: String = grid[0].iter().collect();
+        assert!(names.contains("ANN   █"));
+        assert!(names.contains("█  BOB"));
+    }
+
+    #[test]
+    fn hud_uses_plain_digits_in_ascii() {
+        let mut b = Board::new_static();
+        b.score.left = 3;
+        b.score.right = 12;
+        b.rally = 4;
+        let court = FrameBuffer::new(b.width, b.height, ' ');
+        let fb = compose_screen(&court, &b, &Hud::new(), &RenderStyle::ascii());
+        let s = fb.to_string_lines();
+        let lines: Vec<&str> = s.lines().collect();
+        assert!(lines[0].contains("3      12"));
+        assert!(lines[HUD_HEIGHT - 1].contains("Rally 4"));
+    }
+
+    #[test]
+    fn back_wall_replaces_the_right_paddle() {
+        let mut b = Board::new_static();
+        b.right_wall = true;
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &RenderStyle::ascii());
+        let style = RenderStyle::ascii();
+        assert!((1..b.height - 1).all(|y| fb.get(b.right.x, y) == Some(style.paddle)));
+    }
+
+    #[test]
+    fn bricks_fill_their_cells() {
+        let mut b = Board::new_static();
+        let mut breakout = Breakout::new(1, b.width, b.height);
+        let gone = breakout.bricks.remove(0);
+        b.breakout = Some(breakout);
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &RenderStyle::unicode());
+        let standing = b.breakout.as_ref().unwrap().bricks[0];
+        assert_eq!(fb.get(standing.x + 1, standing.y + 2), Some('▒'));
+        assert_eq!(
+            fb.element(standing.x + 1, standing.y + 2),
+            Some(Element::Brick)
+        );
+        assert_eq!(fb.get(gone.x, gone.y), Some(' '));
+    }
+
+    #[test]
+    fn cells_record_the_element_that_drew_them() {
+        let b = Board::new_static();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &RenderStyle::unicode());
+        assert_eq!(fb.element(0, 0), Some(Element::Border));
+        assert_eq!(fb.element(b.width / 2, 1), Some(Element::Court));
+        assert_eq!(fb.element(b.left.x, b.left.y), Some(Element::Paddle));
+        assert_eq!(fb.element(b.ball.x, b.ball.y), Some(Element::Ball));
+    }
+
+    #[test]
+    fn hud_status_shares_the_row_under_the_scores_with_the_rally() {
+        let mut b = Board::new_static();
+        b.rally = 2;
+        let court = FrameBuffer::new(b.width, b.height, ' ');
+        let hud = Hud::new().with_status("Set 1  Games 0-1");
+        let fb = compose_screen(&court, &b, &hud, &RenderStyle::ascii());
+        let s = fb.to_string_lines();
+        let lines: Vec<&str> = s.lines().collect();
+        assert!(lines[HUD_HEIGHT - 1].contains("Set 1  Games 0-1   Rally 2"));
+    }
+
+    #[test]
+    fn hud_stays_above_the_court() {
+        let mut b = Board::new_static();
+        b.score.left = 88888;
+        b.score.right = 88888;
+        b.rally = 12;
+        let hud = Hud::new()
+            .with_names("A VERY LONG PLAYER NAME", "選手の名前はとても長いです")
+            .with_status("Set 1  Games 0-1");
+        let style = RenderStyle::unicode();
+        let mut court = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut court, &b, &style);
+        let fb = compose_screen(&court, &b, &hud, &style);
+
+        assert_eq!(fb.height(), HUD_HEIGHT + b.height);
+        for y in 0..b.height {
+            for x in 0..b.width {
+                assert_eq!(fb.get(x, HUD_HEIGHT + y), court.get(x, y));
+            }
+        }
+        assert_eq!(fb.get(b.width / 2 + 3, 0), Some('█'));
+    }
 }
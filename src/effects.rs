@@ -0,0 +1,197 @@
+//! Transient visual effects: ball trail, paddle flash and wall sparks.
+//! Driven by BallEvents from the game loop; purely cosmetic, so nothing here
+//! ever feeds back into Board state or physics.
+
+use crate::model::{BallEvent, Board};
+use std::collections::VecDeque;
+
+/// Number of past ball positions kept for the trail.
+pub const TRAIL_LENGTH: usize = 6;
+/// Frames a paddle stays highlighted after a hit.
+const FLASH_FRAMES: u8 = 6;
+/// Frames a wall spark stays visible.
+const SPARK_FRAMES: u8 = 8;
+
+/// A paddle highlighted after the ball bounced off it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaddleFlash {
+    pub left: bool,
+    pub frames_left: u8,
+}
+
+/// A spark on the top or bottom border where the ball bounced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spark {
+    pub x: usize,
+    pub y: usize,
+    pub frames_left: u8,
+}
+
+/// All active effects for the current frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Effects {
+    trail: VecDeque<(usize, usize)>,
+    last_ball: Option<(usize, usize)>,
+    flash: Option<PaddleFlash>,
+    sparks: Vec<Spark>,
+}
+
+impl Effects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance effects by one simulation tick, reacting to this tick's event.
+    pub fn update(&mut self, board: &Board, event: BallEvent) {
+        self.age();
+
+        let pos = (board.ball.x, board.ball.y);
+        match event {
+            BallEvent::LeftGoal | BallEvent::RightGoal => {
+                // Ball teleported to center; a trail across the court would look wrong
+                self.trail.clear();
+                self.last_ball = Some(pos);
+                return;
+            }
+            BallEvent::PaddleBounce => {
+                self.flash = Some(PaddleFlash {
+                    left: board.ball.x < board.width / 2,
+                    frames_left: FLASH_FRAMES,
+                });
+            }
+            BallEvent::WallBounce => {
+                // Spark on the border row the ball just touched
+                let y = if board.ball.y <= board.height / 2 {
+                    0
+                } else {
+                    board.height.saturating_sub(1)
+                };
+                self.sparks.push(Spark {
+                    x: board.ball.x,
+                    y,
+                    frames_left: SPARK_FRAMES,
+                });
+            }
+            BallEvent::BrickHit => {
+                // Spark where the brick broke
+                self.sparks.push(Spark {
+                    x: board.ball.x,
+                    y: board.ball.y,
+                    frames_left: SPARK_FRAMES,
+                });
+            }
+            BallEvent::None => {}
+        }
+
+        // Only extend the trail when the ball actually moved
+        if let Some(last) = self.last_ball {
+            if last != pos {
+                self.trail.push_front(last);
+                self.trail.truncate(TRAIL_LENGTH);
+            }
+        }
+        self.last_ball = Some(pos);
+    }
+
+    /// Count down timers and drop expired effects.
+    fn age(&mut self) {
+        if let Some(flash) = &mut self.flash {
+            flash.frames_left -= 1;
+            if flash.frames_left == 0 {
+                self.flash = None;
+            }
+        }
+        for spark in &mut self.sparks {
+            spark.frames_left -= 1;
+        }
+        self.sparks.retain(|s| s.frames_left > 0);
+    }
+
+    /// Drop every effect (e.g. when a new game starts).
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Past ball positions, newest first.
+    pub fn trail(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.trail.iter().copied()
+    }
+
+    pub fn flash(&self) -> Option<&PaddleFlash> {
+        self.flash.as_ref()
+    }
+
+    pub fn sparks(&self) -> &[Spark] {
+        &self.sparks
+    }
+
+    /// Whether anything would be drawn.
+    pub fn is_active(&self) -> bool {
+        !self.trail.is_empty() || self.flash.is_some() || !self.sparks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trail_keeps_last_positions_newest_first() {
+        let mut board = Board::new_static();
+        let mut fx = Effects::new();
+        for x in 10..20 {
+            board.ball.x = x;
+            fx.update(&board, BallEvent::None);
+        }
+        let trail: Vec<_> = fx.trail().collect();
+        assert_eq!(trail.len(), TRAIL_LENGTH);
+        assert_eq!(trail[0], (18, board.ball.y));
+        assert_eq!(trail[TRAIL_LENGTH - 1], (13, board.ball.y));
+
+        // Ball standing still (between moves) doesn't grow the trail
+        fx.update(&board, BallEvent::None);
+        assert_eq!(fx.trail().next(), Some((18, board.ball.y)));
+    }
+
+    #[test]
+    fn paddle_bounce_flashes_the_hit_paddle_then_expires() {
+        let mut board = Board::new_static();
+        let mut fx = Effects::new();
+        board.ball.x = board.right.x;
+        fx.update(&board, BallEvent::PaddleBounce);
+        assert_eq!(fx.flash().map(|f| f.left), Some(false));
+
+        for _ in 0..FLASH_FRAMES {
+            fx.update(&board, BallEvent::None);
+        }
+        assert!(fx.flash().is_none());
+    }
+
+    #[test]
+    fn wall_bounce_sparks_on_the_touched_border() {
+        let mut board = Board::new_static();
+        let mut fx = Effects::new();
+        board.ball.y = 1;
+        fx.update(&board, BallEvent::WallBounce);
+        board.ball.y = board.height - 2;
+        fx.update(&board, BallEvent::WallBounce);
+
+        let rows: Vec<usize> = fx.sparks().iter().map(|s| s.y).collect();
+        assert_eq!(rows, vec![0, board.height - 1]);
+    }
+
+    #[test]
+    fn goal_clears_trail() {
+        let mut board = Board::new_static();
+        let mut fx = Effects::new();
+        board.ball.x = 5;
+        fx.update(&board, BallEvent::None);
+        board.ball.x = 4;
+        fx.update(&board, BallEvent::None);
+        assert!(fx.trail().next().is_some());
+
+        board.ball.x = board.width / 2;
+        fx.update(&board, BallEvent::LeftGoal);
+        assert!(fx.trail().next().is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Snapshot export: a frame as a self-contained HTML page or SVG image,
+//! colored from the render style's palette.
+
+use crate::framebuffer::{FrameBuffer, TRANSPARENT, WIDE_CONTINUATION};
+use crate::terminal::{color_to_rgb, RenderStyle};
+use crossterm::style::Color;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// SVG cell size in pixels; glyphs are stretched to fit so the grid stays exact.
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 20;
+const FONT_SIZE: usize = 16;
+
+/// Colors used where the palette leaves the terminal default.
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+const DEFAULT_BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+const FONT_FAMILY: &str = "'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+/// Consecutive cells of one row sharing a color.
+struct Run {
+    col: usize,
+    cols: usize,
+    color: Option<Color>,
+    text: String,
+}
+
+/// Split a row into color runs. Spaces keep the current run's color; with
+/// `split_on_space` they end the run instead and are left out.
+fn row_runs(fb: &FrameBuffer, style: &RenderStyle, y: usize, split_on_space: bool) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut open = false;
+    for x in 0..fb.width() {
+        let ch = match fb.get(x, y) {
+            Some(WIDE_CONTINUATION) => {
+                if let Some(run) = runs.last_mut().filter(|_| open) {
+                    run.cols += 1;
+                }
+                continue;
+            }
+            Some(TRANSPARENT) | None => ' ',
+            Some(ch) => ch,
+        };
+        if ch == ' ' && split_on_space {
+            open = false;
+            continue;
+        }
+
+        let color = match fb.element(x, y) {
+            Some(element) if ch != ' ' => style.colors.color(element),
+            _ => None,
+        };
+        match runs.last_mut() {
+            // Spaces join whatever run is open
+            Some(run) if open && (ch == ' ' || run.color == color) => {
+                run.text.push(ch);
+                run.cols += 1;
+            }
+            _ => {
+                runs.push(Run {
+                    col: x,
+                    cols: 1,
+                    color,
+                    text: ch.to_string(),
+                });
+                open = true;
+            }
+        }
+    }
+    runs
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn background(style: &RenderStyle) -> String {
+    hex(style
+        .colors
+        .background
+        .and_then(color_to_rgb)
+        .unwrap_or(DEFAULT_BACKGROUND))
+}
+
+/// Export a frame as a standalone HTML page: a `<pre>` with colored spans.
+pub fn frame_to_html(fb: &FrameBuffer, style: &RenderStyle) -> String {
+    let mut body = String::new();
+    for y in 0..fb.height() {
+        for run in row_runs(fb, style, y, false) {
+            match run.color.and_then(color_to_rgb) {
+                Some(rgb) => {
+                    let _ = write!(
+                        body,
+                        "<span style=\"color:{}\">{}</span>",
+                        hex(rgb),
+                        escape(&run.text)
+                    );
+                }
+                None => body.push_str(&escape(&run.text)),
+            }
+        }
+        body.push('\n');
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Terminal Pong</title>\n\
+         <style>\n\
+         pre {{ display: inline-block; margin: 0; padding: 1em; line-height: 1.2; \
+         font-family: {FONT_FAMILY}; background: {}; color: {}; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <pre>{body}</pre>\n\
+         </body>\n\
+         </html>\n",
+        background(style),
+        hex(DEFAULT_FOREGROUND),
+    )
+}
+
+/// Export a frame as an SVG image with one `<text>` element per color run.
+pub fn frame_to_svg(fb: &FrameBuffer, style: &RenderStyle) -> String {
+    let width = fb.width() * CELL_WIDTH;
+    let height = fb.height() * CELL_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"{FONT_FAMILY}\" font-size=\"{FONT_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        background(style)
+    );
+
+    // Baseline sits about 3/4 down the cell
+    let baseline = CELL_HEIGHT * 3 / 4;
+    for y in 0..fb.height() {
+        for run in row_runs(fb, style, y, true) {
+            let fill = hex(run
+                .color
+                .and_then(color_to_rgb)
+                .unwrap_or(DEFAULT_FOREGROUND));
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" fill=\"{fill}\" textLength=\"{}\" \
+                 lengthAdjust=\"spacingAndGlyphs\">{}</text>",
+                run.col * CELL_WIDTH,
+                y * CELL_HEIGHT + baseline,
+                run.cols * CELL_WIDTH,
+                escape(&run.text)
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Save a frame as `pong-<millis>.html` and `.svg` in `dir`.
+/// Returns the path of the HTML file; the SVG sits next to it.
+pub fn save_snapshot(fb: &FrameBuffer, style: &RenderStyle, dir: &Path) -> io::Result<PathBuf> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let html = dir.join(format!("pong-{millis}.html"));
+    fs::write(&html, frame_to_html(fb, style))?;
+    fs::write(html.with_extension("svg"), frame_to_svg(fb, style))?;
+    Ok(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framebuffer::Element;
+
+    fn sample() -> (FrameBuffer, RenderStyle) {
+        let mut style = RenderStyle::ascii();
+        style.colors.border = Some(Color::Rgb {
+            r: 0x12,
+            g: 0x34,
+            b: 0x56,
+        });
+        style.colors.ball = Some(Color::Yellow);
+        let mut fb = FrameBuffer::from_lines(&["+--+", "-o<-", "+--+"]);
+        // Border and ball as the board draws them; '<' stays text
+        for y in 0..fb.height() {
+            for x in 0..fb.width() {
+                match fb.get(x, y) {
+                    Some(ch @ ('+' | '-')) => fb.set_as(x, y, ch, Element::Border),
+                    Some('o') => fb.set_as(x, y, 'o', Element::Ball),
+                    _ => {}
+                }
+            }
+        }
+        (fb, style)
+    }
+
+    #[test]
+    fn html_wraps_colored_runs_in_spans() {
+        let (fb, style) = sample();
+        let html = frame_to_html(&fb, &style);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<pre><span style=\"color:#123456\">+--+</span>\n\
+             <span style=\"color:#123456\">-</span><span style=\"color:#ffff00\">o</span>\
+             &lt;<span style=\"color:#123456\">-</span>\n"
+        ));
+    }
+
+    #[test]
+    fn html_without_palette_is_plain_text() {
+        let fb = FrameBuffer::from_lines(&["a  b"]);
+        let html = frame_to_html(&fb, &RenderStyle::ascii());
+        assert!(html.contains("<pre>a  b\n</pre>"));
+        assert!(!html.contains("<span"));
+    }
+
+    #[test]
+    fn svg_places_runs_on_the_cell_grid() {
+        let (fb, style) = sample();
+        let svg = frame_to_svg(&fb, &style);
+        assert!(svg.contains("width=\"40\" height=\"60\""));
+        assert!(svg.contains(
+            "<text x=\"10\" y=\"35\" fill=\"#ffff00\" textLength=\"10\" \
+             lengthAdjust=\"spacingAndGlyphs\">o</text>"
+        ));
+        assert!(svg.contains(">&lt;</text>"));
+        assert_eq!(svg.matches("<text").count(), 6);
+    }
+
+    #[test]
+    fn wide_glyphs_span_two_cells() {
+        let fb = FrameBuffer::from_lines(&["世x"]);
+        let svg = frame_to_svg(&fb, &RenderStyle::ascii());
+        assert!(svg.contains("textLength=\"30\" lengthAdjust=\"spacingAndGlyphs\">世x</text>"));
+    }
+}
@@ -1,12 +1,40 @@
 //! FrameBuffer: a simple 2D character buffer for building frames (no ANSI).
 //! - Storage: `Vec<char>`, row-major (index = y * width + x)
 //! - OOB writes/reads are ignored (clipped); invariants guarded with debug_asserts.
+//! - Compositing: `TRANSPARENT` cells are skipped by `blit`, regions give
+//!   clipped sub-views, and `Layers` stacks buffers by z-order.
+//! - Wide glyphs (CJK, emoji) occupy a lead cell plus a `WIDE_CONTINUATION`
+//!   cell; overwriting either half blanks the other so rows never misalign.
+//! - Every cell also records the `Element` that drew it, so renderers color
+//!   cells by what they are rather than by which glyph they hold.
+
+use crate::util::{char_width, str_width};
+
+/// Cell value that lets whatever is underneath show through when blitted.
+pub const TRANSPARENT: char = '\0';
+
+/// Second cell of a double-width glyph; never printed on its own.
+pub const WIDE_CONTINUATION: char = '\u{1}';
+
+/// What a cell belongs to; picks its color from the palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Element {
+    /// Menus, HUD and any other text.
+    #[default]
+    Text,
+    Border,
+    Court,
+    Paddle,
+    Ball,
+    Brick,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FrameBuffer {
     width: usize,
     height: usize,
     cells: Vec<char>,
+    elements: Vec<Element>,
 }
 
 impl FrameBuffer {
@@ -21,9 +49,30 @@ impl FrameBuffer {
             width,
             height,
             cells,
+            elements: vec![Element::Text; len],
         }
     }
 
+    /// Create a fully transparent buffer, for use as an overlay layer.
+    pub fn transparent(width: usize, height: usize) -> Self {
+        Self::new(width, height, TRANSPARENT)
+    }
+
+    /// Create an opaque buffer from text lines, right-padded with spaces to
+    /// the widest line (accounts for Unicode display width).
+    pub fn from_lines(lines: &[&str]) -> Self {
+        let width = lines.iter().map(|l| str_width(l)).max().unwrap_or(0);
+        let mut fb = Self::new(width, lines.len(), ' ');
+        for (y, line) in lines.iter().enumerate() {
+            let mut x = 0;
+            for ch in line.chars() {
+                fb.set(x, y, ch);
+                x += char_width(ch);
+            }
+        }
+        fb
+    }
+
     #[inline]
     pub fn width(&self) -> usize {
         self.width
@@ -37,12 +86,48 @@ impl FrameBuffer {
     /// Clear the entire buffer to `fill`.
     pub fn clear(&mut self, fill: char) {
         self.cells.fill(fill);
+        self.elements.fill(Element::Text);
     }
 
-    /// Set a cell to `ch`; if out-of-bounds, ignore.
+    /// Set a cell to `ch` as text; if out-of-bounds, ignore.
     pub fn set(&mut self, x: usize, y: usize, ch: char) {
-        if let Some(i) = self.idx(x, y) {
+        self.set_as(x, y, ch, Element::Text);
+    }
+
+    /// Set a cell to `ch` drawn as part of `element`; if out-of-bounds, ignore.
+    /// Wide glyphs also claim the next cell; one that would hang off the right
+    /// edge becomes a space. Zero-width characters have no cell and are dropped.
+    pub fn set_as(&mut self, x: usize, y: usize, ch: char, element: Element) {
+        let Some(i) = self.idx(x, y) else {
+            return;
+        };
+        let cw = if ch == TRANSPARENT { 1 } else { char_width(ch) };
+        if cw == 0 {
+            return;
+        }
+
+        let fits = cw == 1 || self.idx(x + 1, y).is_some();
+        self.split_wide(x, y);
+        self.elements[i] = element;
+        if cw == 2 && fits {
+            self.split_wide(x + 1, y);
             self.cells[i] = ch;
+            self.cells[i + 1] = WIDE_CONTINUATION;
+            self.elements[i + 1] = element;
+        } else {
+            self.cells[i] = if fits { ch } else { ' ' };
+        }
+    }
+
+    /// Before (x, y) is overwritten, blank the other half of any wide glyph
+    /// that currently covers it.
+    fn split_wide(&mut self, x: usize, y: usize) {
+        let i = y * self.width + x;
+        if self.cells[i] == WIDE_CONTINUATION && x > 0 {
+            self.cells[i - 1] = ' ';
+        }
+        if x + 1 < self.width && self.cells[i + 1] == WIDE_CONTINUATION {
+            self.cells[i + 1] = ' ';
         }
     }
 
@@ -51,7 +136,52 @@ impl FrameBuffer {
         self.idx(x, y).map(|i| self.cells[i])
     }
 
+    /// Element that drew a cell; None if out-of-bounds.
+    pub fn element(&self, x: usize, y: usize) -> Option<Element> {
+        self.idx(x, y).map(|i| self.elements[i])
+    }
+
+    /// Copy `src` onto this buffer with its top-left at (x, y).
+    /// Transparent source cells are skipped; anything outside is clipped.
+    pub fn blit(&mut self, src: &FrameBuffer, x: usize, y: usize) {
+        for sy in 0..src.height {
+            for sx in 0..src.width {
+                // Continuation cells are rewritten by their lead glyph
+                let i = sy * src.width + sx;
+                let ch = src.cells[i];
+                if ch != TRANSPARENT && ch != WIDE_CONTINUATION {
+                    self.set_as(x + sx, y + sy, ch, src.elements[i]);
+                }
+            }
+        }
+    }
+
+    /// Top-left position that centers `inner` on this buffer.
+    pub fn centered_origin(&self, inner: &FrameBuffer) -> (usize, usize) {
+        (
+            self.width.saturating_sub(inner.width) / 2,
+            self.height.saturating_sub(inner.height) / 2,
+        )
+    }
+
+    /// Mutable view of a rectangle; coordinates are relative to its origin
+    /// and writes never escape it.
+    pub fn region_mut(&mut self, x: usize, y: usize, width: usize, height: usize) -> Region<'_> {
+        // Clamp the view to the buffer so a region never reports phantom cells
+        let width = width.min(self.width.saturating_sub(x));
+        let height = height.min(self.height.saturating_sub(y));
+        Region {
+            fb: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
     /// Convert to a newline-terminated string of lines.
+    /// Transparent cells come out as spaces; wide-glyph continuations are
+    /// skipped since the lead glyph already covers two columns.
     pub fn to_string_lines(&self) -> String {
         let w = self.width;
         let h = self.height;
@@ -59,7 +189,11 @@ impl FrameBuffer {
         for y in 0..h {
             let row_start = y * w;
             for x in 0..w {
-                out.push(self.cells[row_start + x]);
+                match self.cells[row_start + x] {
+                    TRANSPARENT => out.push(' '),
+                    WIDE_CONTINUATION => {}
+                    ch => out.push(ch),
+                }
             }
             out.push('\n');
         }
@@ -78,6 +212,106 @@ impl FrameBuffer {
     }
 }
 
+/// A clipped, mutable rectangle inside a FrameBuffer.
+pub struct Region<'a> {
+    fb: &'a mut FrameBuffer,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Region<'_> {
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Set a cell relative to the region; outside the region is ignored.
+    pub fn set(&mut self, x: usize, y: usize, ch: char) {
+        self.set_as(x, y, ch, Element::Text);
+    }
+
+    /// Set a cell drawn as part of `element`, relative to the region.
+    /// A wide glyph that would cross the region's right edge becomes a space.
+    pub fn set_as(&mut self, x: usize, y: usize, ch: char, element: Element) {
+        if x < self.width && y < self.height {
+            let ch = if x + char_width(ch) > self.width {
+                ' '
+            } else {
+                ch
+            };
+            self.fb.set_as(self.x + x, self.y + y, ch, element);
+        }
+    }
+
+    /// Get a cell relative to the region; None outside it.
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        if x < self.width && y < self.height {
+            self.fb.get(self.x + x, self.y + y)
+        } else {
+            None
+        }
+    }
+
+    /// Fill the whole region with `ch`.
+    pub fn fill(&mut self, ch: char) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.set(x, y, ch);
+            }
+        }
+    }
+
+    /// Blit `src` into the region, clipped to the region's bounds.
+    pub fn blit(&mut self, src: &FrameBuffer, x: usize, y: usize) {
+        for sy in 0..src.height() {
+            for sx in 0..src.width() {
+                match (src.get(sx, sy), src.element(sx, sy)) {
+                    (Some(TRANSPARENT | WIDE_CONTINUATION) | None, _) | (_, None) => {}
+                    (Some(ch), Some(element)) => self.set_as(x + sx, y + sy, ch, element),
+                }
+            }
+        }
+    }
+}
+
+/// A stack of positioned buffers composited in ascending z-order.
+/// Layers with equal z keep their insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct Layers {
+    layers: Vec<(i32, usize, usize, FrameBuffer)>,
+}
+
+impl Layers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer at (x, y) with stacking order `z` (higher draws on top).
+    pub fn push(&mut self, z: i32, x: usize, y: usize, fb: FrameBuffer) {
+        self.layers.push((z, x, y, fb));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Blit every layer onto `base`, lowest z first.
+    pub fn compose_onto(&self, base: &mut FrameBuffer) {
+        let mut order: Vec<&(i32, usize, usize, FrameBuffer)> = self.layers.iter().collect();
+        order.sort_by_key(|(z, ..)| *z);
+        for (_, x, y, fb) in order {
+            base.blit(fb, *x, *y);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +363,113 @@ mod tests {
         assert_eq!(lines[0].chars().collect::<Vec<_>>(), vec!['A', ' ', ' ']);
         assert_eq!(lines[1].chars().collect::<Vec<_>>(), vec![' ', ' ', 'B']);
     }
+
+    #[test]
+    fn blit_skips_transparent_and_clips() {
+        let mut base = FrameBuffer::new(4, 3, '.');
+        let mut top = FrameBuffer::transparent(3, 2);
+        top.set(0, 0, 'A');
+        top.set(2, 1, 'B');
+        base.blit(&top, 2, 1);
+        assert_eq!(base.to_string_lines(), "....\n..A.\n....\n");
+        // 'B' landed at (4, 2), which is clipped
+    }
+
+    #[test]
+    fn elements_follow_their_cells() {
+        let mut fb = FrameBuffer::new(4, 1, ' ');
+        fb.set_as(0, 0, '|', Element::Paddle);
+        fb.set_as(1, 0, '世', Element::Ball);
+        assert_eq!(fb.element(0, 0), Some(Element::Paddle));
+        assert_eq!(fb.element(2, 0), Some(Element::Ball));
+        assert_eq!(fb.element(3, 0), Some(Element::Text));
+
+        // Blitting carries elements; plain `set` draws text
+        let mut base = FrameBuffer::new(6, 1, '.');
+        base.blit(&fb, 1, 0);
+        assert_eq!(base.element(1, 0), Some(Element::Paddle));
+        assert_eq!(base.element(0, 0), Some(Element::Text));
+        base.set(1, 0, '|');
+        assert_eq!(base.element(1, 0), Some(Element::Text));
+        base.clear(' ');
+        assert_eq!(base.element(2, 0), Some(Element::Text));
+    }
+
+    #[test]
+    fn region_translates_and_clips() {
+        let mut fb = FrameBuffer::new(5, 4, '.');
+        {
+            let mut r = fb.region_mut(1, 1, 3, 10);
+            assert_eq!(r.height(), 3); // clamped to the buffer
+            r.fill('#');
+            r.set(0, 0, 'x');
+            r.set(3, 0, 'X'); // outside the region
+            assert_eq!(r.get(0, 0), Some('x'));
+            assert_eq!(r.get(3, 0), None);
+        }
+        assert_eq!(fb.to_string_lines(), ".....\n.x##.\n.###.\n.###.\n");
+    }
+
+    #[test]
+    fn layers_compose_by_z_order() {
+        let mut base = FrameBuffer::new(3, 1, ' ');
+        let mut layers = Layers::new();
+        layers.push(2, 0, 0, FrameBuffer::new(2, 1, 'b'));
+        layers.push(1, 0, 0, FrameBuffer::new(3, 1, 'a'));
+        layers.compose_onto(&mut base);
+        assert_eq!(base.to_string_lines(), "bba\n");
+    }
+
+    #[test]
+    fn from_lines_pads_to_widest_line() {
+        let fb = FrameBuffer::from_lines(&["ab", "╔═╗x"]);
+        assert_eq!(fb.width(), 4);
+        assert_eq!(fb.to_string_lines(), "ab  \n╔═╗x\n");
+        let (x, y) = FrameBuffer::new(10, 6, ' ').centered_origin(&fb);
+        assert_eq!((x, y), (3, 2));
+    }
+
+    #[test]
+    fn wide_glyphs_take_lead_and_continuation_cells() {
+        let mut fb = FrameBuffer::new(5, 1, '.');
+        fb.set(1, 0, '日');
+        assert_eq!(fb.get(1, 0), Some('日'));
+        assert_eq!(fb.get(2, 0), Some(WIDE_CONTINUATION));
+        assert_eq!(fb.to_string_lines(), ".日..\n");
+
+        // Overwriting the continuation blanks the orphaned lead
+        fb.set(2, 0, 'x');
+        assert_eq!(fb.to_string_lines(), ". x..\n");
+
+        // Overwriting the lead blanks the orphaned continuation
+        fb.set(3, 0, '本');
+        fb.set(3, 0, 'y');
+        assert_eq!(fb.to_string_lines(), ". xy \n");
+
+        // No room for the second half at the right edge
+        fb.set(4, 0, 'z');
+        fb.set(4, 0, '語');
+        assert_eq!(fb.to_string_lines(), ". xy \n");
+    }
+
+    #[test]
+    fn zero_width_chars_are_dropped() {
+        let mut fb = FrameBuffer::new(2, 1, '.');
+        fb.set(0, 0, '\u{301}');
+        assert_eq!(fb.get(0, 0), Some('.'));
+    }
+
+    #[test]
+    fn wide_glyphs_survive_blit_and_from_lines() {
+        let overlay = FrameBuffer::from_lines(&["名前", "ab"]);
+        assert_eq!(overlay.width(), 4);
+        let mut base = FrameBuffer::new(6, 2, '.');
+        base.blit(&overlay, 1, 0);
+        assert_eq!(base.to_string_lines(), ".名前.\n.ab  .\n");
+
+        // Each row still spans exactly six display columns
+        for line in base.to_string_lines().lines() {
+            assert_eq!(crate::util::str_width(line), 6);
+        }
+    }
 }
@@ -1,130 +1,543 @@
-//! Game loop: input → update → render at fixed frame rate.
-//! Stage 4: Added ball physics updates.
+//! Game loop: input → update → render for one match at a fixed frame rate.
+//! The surrounding screens and the frame timing live in `app`.
 
+use crate::debug::{draw_debug_overlay, FrameStats};
+use crate::draw::{
+    compose_screen, draw_board, draw_centered_text, draw_countdown, draw_effects, draw_goal_banner,
+};
+use crate::effects::Effects;
+use crate::export::save_snapshot;
+use crate::framebuffer::FrameBuffer;
 use crate::game_session::GameSession;
-use crate::input::{poll_input, InputState};
-use crate::model::Board;
-use std::io;
-use std::thread;
-use std::time::{Duration, Instant};
+use crate::hud::Hud;
+use crate::input::InputState;
+use crate::model::{
+    BallEvent, Board, Breakout, MatchClock, Score, SetScore, Side, BREAKOUT_LEVELS,
+};
+use crate::rules::{GameRules, Handicap};
+use crate::stats::{format_clock, MatchStats};
+use crate::terminal::RenderStyle;
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
 
-const TARGET_FPS: u32 = 60;
-const FRAME_DURATION: Duration = Duration::from_millis(1000 / TARGET_FPS as u64);
+/// Seconds a status notice (e.g. "snapshot saved") stays on screen.
+const NOTICE_SECONDS: u32 = 2;
 
-/// Game state for managing pause functionality.
+/// Tick lengths of the post-goal sequence.
+/// Zero-length phases are skipped, so headless simulations can use `NONE`.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum GameState {
-    Running,
-    Paused,
-    Quit,
+pub struct ServeTimings {
+    /// Ticks the "GOAL!" banner is shown while play is frozen.
+    pub goal_ticks: u32,
+    /// Ticks for the whole 3-2-1 countdown.
+    pub countdown_ticks: u32,
 }
 
-/// Run the main game loop with 60 FPS and conditional rendering.
-/// Returns Ok(()) on clean exit, or an error if something went wrong.
-pub fn run_game_loop(session: &GameSession) -> io::Result<()> {
-    // Initialize game board with moving ball
-    let mut board = Board::new_game();
-    let mut state = GameState::Paused;
-    let mut last_render_state = GameState::Running;
-    let mut last_rendered_board = board.clone(); // Track last rendered board for conditional rendering
-
-    // Initial render
-    session.render_board(&board)?;
-
-    // Game loop
-    let mut _last_frame = Instant::now();
-
-    while state != GameState::Quit {
-        let frame_start = Instant::now();
-
-        // Input phase
-        let input = poll_input()?;
-        handle_input(input, &mut board, &mut state);
-
-        // Update phase - ball physics when game is running
-        if state == GameState::Running {
-            let _ball_event = board.update_ball();
-            // We can use ball_event later for sounds/effects
-        }
-
-        // Render phase - only render when something actually changed
-        match state {
-            GameState::Running => {
-                // Only render if board changed or we're coming from pause
-                if board != last_rendered_board || last_render_state != GameState::Running {
-                    session.render_board(&board)?;
-                    last_rendered_board = board.clone();
-                    last_render_state = GameState::Running;
+impl ServeTimings {
+    /// 1.5s banner and a 3s countdown at `fps` frames per second.
+    pub const fn standard(fps: u32) -> Self {
+        Self {
+            goal_ticks: fps * 3 / 2,
+            countdown_ticks: fps * 3,
+        }
+    }
+
+    /// Serve immediately after a goal.
+    pub const NONE: Self = Self {
+        goal_ticks: 0,
+        countdown_ticks: 0,
+    };
+}
+
+/// Timed sequence between a goal and the next serve; the ball is frozen throughout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServeSequence {
+    Goal { left_scored: bool, ticks_left: u32 },
+    Countdown { ticks_left: u32 },
+}
+
+impl ServeSequence {
+    /// Start the sequence after a goal, or None if the timings skip it entirely.
+    pub fn after_goal(left_scored: bool, timings: ServeTimings) -> Option<Self> {
+        if timings.goal_ticks > 0 {
+            Some(Self::Goal {
+                left_scored,
+                ticks_left: timings.goal_ticks,
+            })
+        } else {
+            Self::countdown(timings)
+        }
+    }
+
+    fn countdown(timings: ServeTimings) -> Option<Self> {
+        (timings.countdown_ticks > 0).then_some(Self::Countdown {
+            ticks_left: timings.countdown_ticks,
+        })
+    }
+
+    /// Advance one tick; None means the ball is served.
+    pub fn tick(self, timings: ServeTimings) -> Option<Self> {
+        match self {
+            Self::Goal {
+                left_scored,
+                ticks_left,
+            } => {
+                if ticks_left > 1 {
+                    Some(Self::Goal {
+                        left_scored,
+                        ticks_left: ticks_left - 1,
+                    })
+                } else {
+                    Self::countdown(timings)
                 }
             }
-            GameState::Paused => {
-                // Only render pause menu when first paused
-                if last_render_state != GameState::Paused {
-                    session.render_pause_menu(&board)?;
-                    last_render_state = GameState::Paused;
-                }
+            Self::Countdown { ticks_left } => (ticks_left > 1).then_some(Self::Countdown {
+                ticks_left: ticks_left - 1,
+            }),
+        }
+    }
+
+    /// Countdown number to show (3, 2, 1), or None during the goal banner.
+    pub fn count(&self, timings: ServeTimings) -> Option<u32> {
+        match *self {
+            Self::Goal { .. } => None,
+            Self::Countdown { ticks_left } => {
+                let step = (timings.countdown_ticks / 3).max(1);
+                Some(ticks_left.div_ceil(step).clamp(1, 3))
             }
-            GameState::Quit => {}
         }
+    }
+}
 
-        // Frame rate limiting
-        let elapsed = frame_start.elapsed();
-        if elapsed < FRAME_DURATION {
-            thread::sleep(FRAME_DURATION - elapsed);
+/// What was last presented, so frames are only drawn when something changed.
+#[derive(Debug, Clone, PartialEq)]
+struct RenderedState {
+    board: Board,
+    effects: Effects,
+    sequence: Option<ServeSequence>,
+    notice: Option<String>,
+    debug: bool,
+}
+
+/// A match in progress: the simulation plus its cosmetic and debug state.
+pub struct Match {
+    pub board: Board,
+    pub stats: MatchStats,
+    /// Score that wins the match (or each game when playing in sets); None
+    /// plays on forever.
+    pub target_score: Option<u32>,
+    /// Player 1 plays on the right (sides swap for a rematch).
+    pub swapped: bool,
+    effects: Effects,
+    sequence: Option<ServeSequence>,
+    timings: ServeTimings,
+    /// Status text on the bottom border and the ticks it has left.
+    notice: Option<(String, u32)>,
+    debug: bool,
+    snapshot_requested: bool,
+    last_event: BallEvent,
+    last_rendered: Option<RenderedState>,
+}
+
+impl Match {
+    /// New match under `rules` with the ball in play.
+    pub fn new(rules: GameRules, target_score: Option<u32>) -> Self {
+        Self {
+            board: Board::new_game(rules),
+            stats: MatchStats::new(),
+            target_score,
+            swapped: false,
+            effects: Effects::new(),
+            sequence: None,
+            timings: ServeTimings::standard(rules.fps),
+            notice: None,
+            debug: false,
+            snapshot_requested: false,
+            last_event: BallEvent::None,
+            last_rendered: None,
         }
+    }
 
-        _last_frame = frame_start;
+    /// Play the match as best-of-`best_of` sets of games to the target score.
+    pub fn play_sets(&mut self, best_of: u32) {
+        self.board.sets = Some(SetScore::new(best_of, self.board.rules.games_per_set));
     }
 
-    // Cleanup handled by GameSession Drop
+    /// Play for `seconds` of simulated time; the higher score then wins, and
+    /// a tie goes to sudden-death overtime.
+    pub fn play_timed(&mut self, seconds: u32) {
+        self.board.clock = Some(MatchClock::new(seconds, self.board.rules.fps));
+    }
 
-    Ok(())
-}
+    /// Practice alone against a back wall, starting from the `best` run so far.
+    pub fn play_squash(&mut self, best: u32) {
+        self.board.right_wall = true;
+        self.board.score = Score {
+            left: 0,
+            right: best,
+        };
+    }
 
-/// Process input and update game state.
-fn handle_input(input: InputState, board: &mut Board, state: &mut GameState) {
-    // Check for quit first (highest priority)
-    if input.quit {
-        *state = GameState::Quit;
-        return;
+    /// Clear columns of bricks in front of a back wall, level by level.
+    pub fn play_breakout(&mut self) {
+        self.board.right_wall = true;
+        self.board.breakout = Some(Breakout::new(1, self.board.width, self.board.height));
+        self.board.score = Score::default();
     }
 
-    // Check for pause toggle
-    if input.pause {
-        *state = match *state {
-            GameState::Running => GameState::Paused,
-            GameState::Paused => GameState::Running,
-            GameState::Quit => GameState::Quit,
+    /// Give each side a handicap: paddle size and speed, assist and a head
+    /// start, all recorded in the stats.
+    pub fn set_handicaps(&mut self, left: Handicap, right: Handicap) {
+        self.board.set_handicaps(left, right);
+        self.stats.left.handicap = left;
+        self.stats.right.handicap = right;
+        self.board.score = self.start_score();
+    }
+
+    /// Score each game starts from: the handicap head starts, kept short of
+    /// winning outright.
+    fn start_score(&self) -> Score {
+        let cap = self.target_score.map_or(u32::MAX, |t| t.saturating_sub(1));
+        Score {
+            left: self.board.left_handicap.start_score.min(cap),
+            right: self.board.right_handicap.start_score.min(cap),
+        }
+    }
+
+    /// Switch rules mid-match. The target score is left alone.
+    pub fn set_rules(&mut self, rules: GameRules) {
+        self.board.set_rules(rules);
+        self.timings = ServeTimings::standard(rules.fps);
+    }
+
+    /// Play one frame: paddle input, then physics. `elapsed` is the wall time
+    /// since the previous frame, counted as play time.
+    pub fn tick(&mut self, mut input: InputState, elapsed: Duration) -> BallEvent {
+        // The ball can't be served before the banner and countdown are over
+        if self.sequence.is_some() {
+            input.left_serve = false;
+            input.right_serve = false;
+        }
+        handle_input(input, &mut self.board);
+        if input.toggle_debug {
+            self.debug = !self.debug;
+        }
+        self.snapshot_requested |= input.screenshot;
+
+        let rally = self.board.rally;
+        let event = update(
+            &mut self.board,
+            &mut self.effects,
+            &mut self.sequence,
+            self.timings,
+        );
+        // A goal resets the board's rally, so it's credited with the count from before
+        let rally = match event {
+            BallEvent::LeftGoal | BallEvent::RightGoal => rally,
+            _ => self.board.rally,
         };
+        self.stats.record(event, rally);
+        if let (BallEvent::PaddleBounce, Some((side, location))) = (event, self.board.last_hit) {
+            self.stats.record_hit(side, location);
+        }
+        self.stats.play_time += elapsed;
+        if matches!(event, BallEvent::LeftGoal | BallEvent::RightGoal) {
+            self.end_game_if_won();
+        }
+        if event != BallEvent::None {
+            self.last_event = event;
+        }
+
+        let notice = self.notice.take();
+        self.notice = notice.and_then(|(text, ticks)| (ticks > 1).then(|| (text, ticks - 1)));
+        event
+    }
+
+    /// When playing in sets, close a game that reached the target score:
+    /// award it, and unless that won the match, start the next game with the
+    /// players changing ends.
+    fn end_game_if_won(&mut self) {
+        let (Some(target), Some(sets)) = (self.target_score, self.board.sets.as_mut()) else {
+            return;
+        };
+        let score = self.board.score;
+        let side = if score.left >= target {
+            Side::Left
+        } else if score.right >= target {
+            Side::Right
+        } else {
+            return;
+        };
+        if sets.win_game(side) {
+            return;
+        }
+        sets.swap_sides();
+        let text = format!("Game to {} - change ends", self.player_name(side));
+        let (left, right) = (self.board.left_handicap, self.board.right_handicap);
+        self.board.set_handicaps(right, left);
+        self.board.score = self.start_score();
+        // The server keeps the serve from the other end
+        if let Some(serve) = self.board.serve {
+            self.board.start_serve(serve.side.opposite());
+        }
+        self.stats.swap_sides();
+        self.swapped = !self.swapped;
+        self.notice = Some((text, self.board.rules.fps * NOTICE_SECONDS));
     }
 
-    // Process movement only when running
-    if *state == GameState::Running {
-        // Process all active inputs in this frame
-        // Note: if both up and down are pressed, they cancel out (no movement)
-        if input.left_up && !input.left_down {
-            board.move_left_paddle_up();
-        } else if input.left_down && !input.left_up {
-            board.move_left_paddle_down();
+    /// The side that won the match, once its goal banner is over: the one
+    /// reaching the target score, taking enough sets, or ahead when the
+    /// clock runs out. Clearing the last breakout level wins it for the left.
+    pub fn winner(&self) -> Option<Side> {
+        if matches!(self.sequence, Some(ServeSequence::Goal { .. })) {
+            return None;
         }
+        if let Some(sets) = &self.board.sets {
+            return sets.winner();
+        }
+        if let Some(breakout) = &self.board.breakout {
+            return breakout.cleared().then_some(Side::Left);
+        }
+        let score = self.board.score;
+        if let Some(clock) = &self.board.clock {
+            if !clock.expired() {
+                return None;
+            }
+            return match score.left.cmp(&score.right) {
+                Ordering::Greater => Some(Side::Left),
+                Ordering::Less => Some(Side::Right),
+                Ordering::Equal => None,
+            };
+        }
+        let target = self.target_score?;
+        if self.board.score.left >= target {
+            Some(Side::Left)
+        } else if self.board.score.right >= target {
+            Some(Side::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Which player is on `side`.
+    pub fn player_name(&self, side: Side) -> &'static str {
+        match (side, self.swapped) {
+            (Side::Left, false) | (Side::Right, true) => "Player 1",
+            (Side::Left, true) | (Side::Right, false) => "Player 2",
+        }
+    }
+
+    /// Scoreboard with short player names, so swapped sides are visible (or
+    /// the run and best in squash, bricks and misses in breakout),
+    /// plus games and sets, the time left or the level when the match has them.
+    fn hud(&self) -> Hud {
+        let short = |side| self.player_name(side).replace("Player ", "P");
+        if let Some(breakout) = &self.board.breakout {
+            return Hud::new()
+                .with_names("BRICKS", "MISSES")
+                .with_status(&format!(
+                    "Level {}/{}  Bricks left {}",
+                    breakout.level,
+                    BREAKOUT_LEVELS,
+                    breakout.bricks.len()
+                ));
+        }
+        if self.board.right_wall {
+            return Hud::new().with_names("RUN", "BEST");
+        }
+        let hud = Hud::new().with_names(&short(Side::Left), &short(Side::Right));
+        let status = if let Some(sets) = &self.board.sets {
+            format!(
+                "Set {}  Games {}-{}  Sets {}-{}",
+                sets.current_set(),
+                sets.games.left,
+                sets.games.right,
+                sets.sets.left,
+                sets.sets.right
+            )
+        } else if let Some(clock) = &self.board.clock {
+            if clock.overtime {
+                "Sudden death - next goal wins".to_string()
+            } else {
+                format!(
+                    "Time {}",
+                    format_clock(clock.remaining(self.board.rules.fps))
+                )
+            }
+        } else {
+            return hud;
+        };
+        hud.with_status(&status)
+    }
+
+    /// Force the next `render` to draw, e.g. after a menu covered the board.
+    pub fn invalidate(&mut self) {
+        self.last_rendered = None;
+    }
+
+    /// Compose the frame for the current state.
+    pub fn compose(&self, style: &RenderStyle, frame_stats: &FrameStats) -> FrameBuffer {
+        let notice = self.notice.as_ref().map(|(text, _)| text.as_str());
+        let mut court = compose_court(
+            &self.board,
+            &self.effects,
+            self.sequence,
+            self.timings,
+            style,
+            notice,
+        );
+        if self.debug {
+            draw_debug_overlay(&mut court, &self.board, frame_stats, self.last_event, style);
+        }
+        compose_screen(&court, &self.board, &self.hud(), style)
+    }
+
+    /// Present the frame if anything changed since the last one.
+    pub fn render<W: Write>(
+        &mut self,
+        session: &GameSession<W>,
+        frame_stats: &FrameStats,
+    ) -> io::Result<()> {
+        let style = session.style();
+        if std::mem::take(&mut self.snapshot_requested) {
+            // Snapshot the game itself, without any notice on top
+            let court = compose_court(
+                &self.board,
+                &self.effects,
+                self.sequence,
+                self.timings,
+                &style,
+                None,
+            );
+            let fb = compose_screen(&court, &self.board, &self.hud(), &style);
+            let text = match save_snapshot(&fb, &style, Path::new(".")) {
+                Ok(path) => {
+                    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                    format!("Saved {stem}.html and .svg")
+                }
+                Err(err) => format!("Snapshot failed: {err}"),
+            };
+            self.notice = Some((text, self.board.rules.fps * NOTICE_SECONDS));
+        }
+
+        // Compare only the notice text so its countdown alone doesn't force a redraw
+        let state = RenderedState {
+            board: self.board.clone(),
+            effects: self.effects.clone(),
+            sequence: self.sequence,
+            notice: self.notice.as_ref().map(|(text, _)| text.clone()),
+            debug: self.debug,
+        };
+        // Timing changes every frame while the debug overlay is up
+        if self.debug || self.last_rendered.as_ref() != Some(&state) {
+            session.render_frame(&self.compose(&style, frame_stats))?;
+            self.last_rendered = Some(state);
+        }
+        Ok(())
+    }
+}
+
+/// Advance the simulation one tick: ball physics, or the post-goal sequence.
+fn update(
+    board: &mut Board,
+    effects: &mut Effects,
+    sequence: &mut Option<ServeSequence>,
+    timings: ServeTimings,
+) -> BallEvent {
+    // Ball stays frozen until the sequence finishes, held on the server's
+    // paddle as it moves
+    if let Some(current) = *sequence {
+        board.hold_ball();
+        *sequence = current.tick(timings);
+        return BallEvent::None;
+    }
+
+    board.assist_paddles();
+    board.tick_clock();
+    let ball_event = board.update_ball();
+    // Effects only observe the board; physics never sees them
+    effects.update(board, ball_event);
+
+    match ball_event {
+        // A miss against the back wall isn't a goal for anyone; the held
+        // serve is pause enough
+        BallEvent::LeftGoal if board.right_wall => {}
+        BallEvent::LeftGoal => *sequence = ServeSequence::after_goal(false, timings),
+        BallEvent::RightGoal => *sequence = ServeSequence::after_goal(true, timings),
+        _ => {}
+    }
+    ball_event
+}
+
+/// Compose the in-game court: board, effects, then any serve overlay and
+/// a status notice on the bottom border. The HUD goes above it.
+fn compose_court(
+    board: &Board,
+    effects: &Effects,
+    sequence: Option<ServeSequence>,
+    timings: ServeTimings,
+    style: &RenderStyle,
+    notice: Option<&str>,
+) -> FrameBuffer {
+    let mut fb = FrameBuffer::new(board.width, board.height, ' ');
+    draw_board(&mut fb, board, style);
+    draw_effects(&mut fb, board, effects, style);
 
-        if input.right_up && !input.right_down {
-            board.move_right_paddle_up();
-        } else if input.right_down && !input.right_up {
-            board.move_right_paddle_down();
+    match sequence {
+        Some(ServeSequence::Goal { left_scored, .. }) => {
+            draw_goal_banner(&mut fb, left_scored, style)
+        }
+        Some(seq) => {
+            if let Some(count) = seq.count(timings) {
+                draw_countdown(&mut fb, count, style);
+            }
         }
+        None => {}
+    }
+    if let Some(text) = notice {
+        let bottom = fb.height() - 1;
+        draw_centered_text(&mut fb, &format!(" {text} "), bottom);
+    }
+    fb
+}
+
+/// Move paddles and launch serves from this frame's input.
+/// Pause and quit are screen changes, handled by the app.
+fn handle_input(input: InputState, board: &mut Board) {
+    // Process all active inputs in this frame
+    // Note: if both up and down are pressed, they cancel out (no movement)
+    if input.left_up && !input.left_down {
+        board.move_left_paddle_up();
+    } else if input.left_down && !input.left_up {
+        board.move_left_paddle_down();
+    }
+
+    if input.right_up && !input.right_down {
+        board.move_right_paddle_up();
+    } else if input.right_down && !input.right_up {
+        board.move_right_paddle_down();
+    }
+
+    // Serve keys only do something for the player holding the ball
+    if input.left_serve {
+        board.launch_serve(Side::Left);
+    }
+    if input.right_serve {
+        board.launch_serve(Side::Right);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::tests::ball_before_goal;
     use crate::model::Board;
 
     #[test]
     fn test_input_handling() {
         let mut board = Board::new_static();
-        let mut state = GameState::Running;
 
         let initial_left_y = board.left.y;
         let initial_right_y = board.right.y;
@@ -132,38 +545,276 @@ mod tests {
         // Test left paddle movement
         let mut input = InputState::new();
         input.left_up = true;
-        handle_input(input, &mut board, &mut state);
+        handle_input(input, &mut board);
         assert!(board.left.y < initial_left_y);
 
         // Test right paddle movement
         let mut input = InputState::new();
         input.right_down = true;
-        handle_input(input, &mut board, &mut state);
+        handle_input(input, &mut board);
         assert!(board.right.y > initial_right_y);
 
-        // Test pause
-        let mut input = InputState::new();
-        input.pause = true;
-        handle_input(input, &mut board, &mut state);
-        assert_eq!(state, GameState::Paused);
-
-        // Movement should not work when paused
-        let paused_left_y = board.left.y;
+        // Opposite keys cancel out
+        let left_y = board.left.y;
         let mut input = InputState::new();
+        input.left_up = true;
         input.left_down = true;
-        handle_input(input, &mut board, &mut state);
-        assert_eq!(board.left.y, paused_left_y);
+        handle_input(input, &mut board);
+        assert_eq!(board.left.y, left_y);
 
-        // Unpause
+        // Only the player holding the ball can serve it
+        board.start_serve(Side::Right);
         let mut input = InputState::new();
-        input.pause = true;
-        handle_input(input, &mut board, &mut state);
-        assert_eq!(state, GameState::Running);
+        input.left_serve = true;
+        handle_input(input, &mut board);
+        assert!(board.serve.is_some());
+        input.right_serve = true;
+        handle_input(input, &mut board);
+        assert_eq!(board.serve, None);
+        // Pause and quit are covered by the app's screen tests
+    }
 
-        // Test quit
-        let mut input = InputState::new();
-        input.quit = true;
-        handle_input(input, &mut board, &mut state);
-        assert_eq!(state, GameState::Quit);
+    #[test]
+    fn match_ends_after_the_winning_goal_banner() {
+        let mut game = Match::new(GameRules::CLASSIC, Some(1));
+        game.timings = ServeTimings {
+            goal_ticks: 2,
+            countdown_ticks: 0,
+        };
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.board.score.left, 1);
+        assert_eq!(game.winner(), None); // Banner still showing
+        game.tick(InputState::new(), Duration::ZERO);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.winner(), Some(Side::Left));
+        assert_eq!(game.player_name(Side::Left), "Player 1");
+        assert_eq!(Match::new(GameRules::CLASSIC, None).winner(), None);
+    }
+
+    #[test]
+    fn sets_change_ends_after_each_game() {
+        let rules = GameRules {
+            games_per_set: 1,
+            ..GameRules::CLASSIC
+        };
+        let mut game = Match::new(rules, Some(1));
+        game.timings = ServeTimings::NONE;
+        game.play_sets(3);
+        let head_start = Handicap {
+            start_score: 5,
+            paddle_height: 1,
+            ..Handicap::default()
+        };
+        game.set_handicaps(Handicap::default(), head_start);
+        // The head start is capped below the one-point target
+        assert_eq!(game.board.score, Score::default());
+
+        // Player 1 takes the first set and moves to the right
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        let sets = game.board.sets.clone().unwrap();
+        assert_eq!(sets.sets, Score { left: 0, right: 1 });
+        // Player 2's handicap moves to the left with them
+        assert_eq!(game.board.left_handicap, head_start);
+        assert_eq!(game.stats.left.handicap, head_start);
+        assert_eq!(game.board.left.height, 6);
+        assert_eq!(game.board.score, Score::default());
+        assert_eq!(game.player_name(Side::Right), "Player 1");
+        assert_eq!(game.winner(), None);
+        assert!(game.hud().status.unwrap().starts_with("Set 2"));
+
+        // Player 2 levels from the left, then Player 1 wins back on the left
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert!(!game.swapped);
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.winner(), Some(Side::Left));
+        assert_eq!(game.player_name(Side::Left), "Player 1");
+        assert_eq!(game.board.score.left, 1);
+        assert_eq!(game.board.sets.unwrap().current_set(), 3);
+    }
+
+    #[test]
+    fn timed_match_goes_to_sudden_death_on_a_tie() {
+        let mut game = Match::new(GameRules::CLASSIC, None);
+        game.timings = ServeTimings::NONE;
+        game.play_timed(1);
+        assert_eq!(game.hud().status.as_deref(), Some("Time 00:01"));
+
+        // Keep the ball mid-court so the second runs out at 0-0
+        for _ in 0..60 {
+            game.board.ball.x = 40;
+            game.tick(InputState::new(), Duration::ZERO);
+        }
+        assert!(game.board.clock.unwrap().overtime);
+        assert_eq!(game.winner(), None);
+        assert_eq!(
+            game.hud().status.as_deref(),
+            Some("Sudden death - next goal wins")
+        );
+
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.winner(), Some(Side::Left));
+    }
+
+    #[test]
+    fn goals_credit_the_rally_the_board_counted() {
+        let mut game = Match::new(GameRules::CLASSIC, None);
+        game.board.rally = 4;
+        ball_before_goal(&mut game.board, Side::Left);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.last_event, BallEvent::LeftGoal);
+        assert_eq!(game.board.rally, 0);
+        assert_eq!(game.stats.finished_rally_hits, 4);
+        assert_eq!(game.stats.points_played, 1);
+    }
+
+    #[test]
+    fn squash_misses_skip_the_goal_banner() {
+        let mut game = Match::new(GameRules::CLASSIC, None);
+        game.play_squash(3);
+        game.board.score.left = 2;
+        ball_before_goal(&mut game.board, Side::Left);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.last_event, BallEvent::LeftGoal);
+        // The run ends, the best one stands and play goes on without a banner
+        assert_eq!(game.board.score, Score { left: 0, right: 3 });
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.sequence, None);
+        assert_eq!(game.board.serve.map(|serve| serve.side), Some(Side::Left));
+    }
+
+    #[test]
+    fn breakout_is_won_by_clearing_the_last_level() {
+        let mut game = Match::new(GameRules::CLASSIC, None);
+        game.play_breakout();
+        let hud = game.hud();
+        assert_eq!(hud.status.as_deref(), Some("Level 1/3  Bricks left 6"));
+        assert_eq!(game.winner(), None);
+
+        // A miss is counted without a goal banner
+        ball_before_goal(&mut game.board, Side::Left);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert_eq!(game.board.score.right, 1);
+        assert_eq!(game.sequence, None);
+
+        // One brick left on the last level, right in the ball's path
+        let breakout = game.board.breakout.as_mut().unwrap();
+        breakout.level = BREAKOUT_LEVELS;
+        breakout.bricks.truncate(1);
+        let brick = breakout.bricks[0];
+        game.board.serve = None;
+        game.board.ball.x = brick.x - 1;
+        game.board.ball.y = brick.y;
+        game.board.ball.dx = 1;
+        game.board.ball.dy = 0;
+        game.board.frame_counter = 1;
+        assert_eq!(
+            game.tick(InputState::new(), Duration::ZERO),
+            BallEvent::BrickHit
+        );
+        assert_eq!(game.winner(), Some(Side::Left));
+    }
+
+    #[test]
+    fn serve_sequence_runs_banner_then_countdown() {
+        let timings = ServeTimings {
+            goal_ticks: 2,
+            countdown_ticks: 6,
+        };
+        let mut seq = ServeSequence::after_goal(true, timings);
+        assert_eq!(
+            seq,
+            Some(ServeSequence::Goal {
+                left_scored: true,
+                ticks_left: 2
+            })
+        );
+
+        let mut counts = Vec::new();
+        while let Some(current) = seq {
+            counts.push(current.count(timings));
+            seq = current.tick(timings);
+        }
+        assert_eq!(
+            counts,
+            vec![
+                None,
+                None,
+                Some(3),
+                Some(3),
+                Some(2),
+                Some(2),
+                Some(1),
+                Some(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn no_timings_skip_the_sequence() {
+        assert_eq!(ServeSequence::after_goal(false, ServeTimings::NONE), None);
+    }
+
+    #[test]
+    fn ball_is_frozen_until_serve() {
+        let timings = ServeTimings {
+            goal_ticks: 3,
+            countdown_ticks: 3,
+        };
+        let mut board = Board::new_static();
+        let mut effects = Effects::new();
+        let mut sequence = None;
+
+        ball_before_goal(&mut board, Side::Right);
+        let event = update(&mut board, &mut effects, &mut sequence, timings);
+        assert_eq!(event, BallEvent::RightGoal);
+        assert!(sequence.is_some());
+
+        let served_from = board.ball.clone();
+        for _ in 0..6 {
+            update(&mut board, &mut effects, &mut sequence, timings);
+            assert_eq!(board.ball, served_from);
+        }
+        assert_eq!(sequence, None);
+
+        // Then the loser holds the ball until serving it
+        update(&mut board, &mut effects, &mut sequence, timings);
+        assert_eq!(board.ball.x, board.right.x - 1);
+        let held = board.ball.clone();
+        assert!(board.launch_serve(Side::Right));
+        update(&mut board, &mut effects, &mut sequence, timings);
+        update(&mut board, &mut effects, &mut sequence, timings);
+        assert_ne!(board.ball, held);
+    }
+
+    #[test]
+    fn held_ball_follows_the_server_through_the_countdown() {
+        let mut game = Match::new(GameRules::CLASSIC, None);
+        game.timings = ServeTimings {
+            goal_ticks: 2,
+            countdown_ticks: 3,
+        };
+        ball_before_goal(&mut game.board, Side::Right);
+        game.tick(InputState::new(), Duration::ZERO);
+        assert!(game.sequence.is_some());
+        assert_eq!(game.board.serve.map(|serve| serve.side), Some(Side::Right));
+
+        // The server moves and tries to serve early: the ball stays on the paddle
+        let input = InputState {
+            right_down: true,
+            right_serve: true,
+            ..InputState::new()
+        };
+        for _ in 0..4 {
+            game.tick(input, Duration::ZERO);
+            let right = &game.board.right;
+            assert_eq!(game.board.ball.x, right.x - 1);
+            assert_eq!(game.board.ball.y, right.y + right.height / 2);
+        }
+        assert!(game.board.serve.is_some());
     }
 }
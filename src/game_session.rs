@@ -1,18 +1,32 @@
 //! GameSession: Unified management of alternate screen, raw mode, and terminal output.
 //! This ensures consistent terminal state throughout the game lifecycle.
+//! Output goes to any `io::Write` (stdout by default), so the whole render
+//! pipeline can run headless.
 
-use crate::framebuffer::FrameBuffer;
+use crate::draw::{compose_screen, draw_board, draw_board_with_message};
+use crate::framebuffer::{FrameBuffer, Layers};
+use crate::hud::Hud;
 use crate::model::Board;
-use crate::render::render_with_message_to_string;
-use crate::terminal::RenderStyle;
-use crate::util::str_width;
+use crate::recorder::CastRecorder;
+use crate::render::frame_to_ansi;
+use crate::stats::{format_clock, MatchStats};
+use crate::terminal::{RenderStyle, TerminalCapabilities};
+use crate::theme::Theme;
+use crate::widgets::{KeyHints, Menu, Panel, Widget};
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode},
+    event::{
+        self, Event, KeyCode, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute, queue,
     terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::io::{self, Write};
+use std::cell::{Cell, RefCell};
+use std::io::{self, Stdout, Write};
+
+/// Pause menu entries, in order.
+pub const PAUSE_MENU: [&str; 3] = ["Resume", "Settings", "Main menu"];
 
 // Synchronized Output escape sequences
 const SYNC_BEGIN: &str = "\x1b[?2026h";
@@ -20,8 +34,17 @@ const SYNC_END: &str = "\x1b[?2026l";
 
 /// Manages the terminal session for the game, including alternate screen,
 /// raw mode, cursor visibility, and proper line ending conversion.
-pub struct GameSession {
-    // No fields needed - just lifecycle management
+pub struct GameSession<W: Write = Stdout> {
+    out: RefCell<W>,
+    /// Set when this session switched the terminal into game mode and must
+    /// restore it on drop.
+    owns_terminal: bool,
+    recorder: RefCell<Option<CastRecorder>>,
+    caps: TerminalCapabilities,
+    style: Cell<RenderStyle>,
+    /// The braille ball is available; themes can only turn it off.
+    smooth_ball: bool,
+    theme_name: RefCell<String>,
 }
 
 impl GameSession {
@@ -37,10 +60,79 @@ impl GameSession {
         // Hide cursor for clean display
         execute!(out, Hide)?;
 
+        // Probe the terminal once; raw mode keeps the replies off the screen
+        let caps = TerminalCapabilities::detect();
+        if caps.kitty_keyboard {
+            // Report key releases so held keys move paddles without momentum guesses
+            execute!(
+                out,
+                PushKeyboardEnhancementFlags(
+                    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                )
+            )?;
+        }
+
         // Initialize momentum tracker for smooth input
-        crate::input::init_momentum();
+        crate::input::init_input(&caps);
+
+        let mut session = Self::with_writer(out, caps);
+        session.owns_terminal = true;
+        Ok(session)
+    }
+}
+
+impl<W: Write> GameSession<W> {
+    /// Session that renders into `out` as a terminal with `caps` would see it.
+    /// The real terminal is left untouched, so this works headless.
+    pub fn with_writer(out: W, caps: TerminalCapabilities) -> Self {
+        let mut style = RenderStyle::for_capabilities(&caps);
+        style.colors = style.colors.adapted(caps.colors);
+
+        Self {
+            out: RefCell::new(out),
+            owns_terminal: false,
+            recorder: RefCell::new(None),
+            caps,
+            style: Cell::new(style),
+            smooth_ball: style.smooth_ball,
+            theme_name: RefCell::new("classic".to_string()),
+        }
+    }
+
+    /// Tee all further output into `recorder`.
+    pub fn start_recording(&self, recorder: CastRecorder) {
+        *self.recorder.borrow_mut() = Some(recorder);
+    }
+
+    /// The underlying writer, e.g. to inspect what a headless session wrote.
+    pub fn writer(&self) -> std::cell::RefMut<'_, W> {
+        self.out.borrow_mut()
+    }
+
+    /// Capabilities detected when the session was entered.
+    pub fn capabilities(&self) -> TerminalCapabilities {
+        self.caps
+    }
+
+    /// Active render style for every frame this session draws.
+    pub fn style(&self) -> RenderStyle {
+        self.style.get()
+    }
+
+    /// Name of the active theme.
+    pub fn theme_name(&self) -> String {
+        self.theme_name.borrow().clone()
+    }
 
-        Ok(Self {})
+    /// Switch theme; glyphs fall back to ASCII and colors are reduced if the
+    /// terminal needs it.
+    pub fn set_theme(&self, theme: &Theme) {
+        let mut style = theme.resolve(self.caps.unicode);
+        style.smooth_ball &= self.smooth_ball;
+        style.colors = style.colors.adapted(self.caps.colors);
+        self.style.set(style);
+        *self.theme_name.borrow_mut() = theme.name.clone();
     }
 
     // ============================================================================
@@ -52,94 +144,38 @@ impl GameSession {
         content.replace('\n', "\r\n")
     }
 
-    /// Strip trailing newline if present
-    fn strip_trailing_newline<'a>(&self, content: &'a str) -> &'a str {
-        content.strip_suffix("\r\n").unwrap_or(content)
-    }
-
-    /// Write content with optional synchronized output
+    /// Write content with optional synchronized output.
+    /// Sync markers are only sent to terminals that confirmed support.
+    /// Every byte the session shows passes through here, so a recording
+    /// sees exactly what the terminal saw.
     fn write_output(&self, content: &[u8], synchronized: bool) -> io::Result<()> {
-        let mut out = io::stdout();
+        let synchronized = synchronized && self.caps.synchronized_output;
 
+        let mut bytes = Vec::with_capacity(content.len() + SYNC_BEGIN.len() + SYNC_END.len());
         if synchronized {
-            out.write_all(SYNC_BEGIN.as_bytes())?;
+            bytes.extend_from_slice(SYNC_BEGIN.as_bytes());
         }
-
-        out.write_all(content)?;
-
+        bytes.extend_from_slice(content);
         if synchronized {
-            out.write_all(SYNC_END.as_bytes())?;
+            bytes.extend_from_slice(SYNC_END.as_bytes());
         }
 
-        out.flush()
-    }
-
-    /// Render board to framebuffer
-    fn render_board_to_buffer(&self, board: &Board) -> FrameBuffer {
-        let style = RenderStyle::auto();
-        let mut fb = FrameBuffer::new(board.width, board.height, ' ');
-        crate::draw::draw_board(&mut fb, board, &style);
-        fb
-    }
-
-    /// Apply centered overlay to base content
-    fn apply_centered_overlay(
-        &self,
-        base_lines: Vec<&str>,
-        overlay_lines: Vec<String>,
-        board_width: usize,
-        board_height: usize,
-    ) -> Vec<String> {
-        let mut result: Vec<String> = base_lines.iter().map(|s| s.to_string()).collect();
-
-        // Calculate overlay dimensions and position
-        let line_widths: Vec<usize> = overlay_lines
-            .iter()
-            .map(|l| str_width(l.as_str()))
-            .collect();
-        let menu_height = overlay_lines.len();
-        let menu_width = line_widths.iter().copied().max().unwrap_or(0);
-
-        // Normalize overlay lines (right-pad to uniform width)
-        let mut normalized_lines = overlay_lines;
-        for (line, width) in normalized_lines.iter_mut().zip(line_widths.iter()) {
-            if *width < menu_width {
-                let pad = " ".repeat(menu_width - *width);
-                line.push_str(&pad);
-            }
-        }
+        let mut out = self.out.borrow_mut();
+        out.write_all(&bytes)?;
+        out.flush()?;
 
-        // Calculate centered position
-        let start_y = board_height.saturating_sub(menu_height) / 2;
-        let start_x = board_width.saturating_sub(menu_width) / 2;
-
-        // Apply overlay to base content
-        for (i, overlay_line) in normalized_lines.iter().enumerate() {
-            let y = start_y + i;
-            if y < result.len() {
-                let line = &mut result[y];
-                // Replace the portion of the base line with the overlay
-                if start_x < line.len() {
-                    let line_chars: Vec<char> = line.chars().collect();
-                    let overlay_chars: Vec<char> = overlay_line.chars().collect();
-                    let mut new_line = String::new();
-
-                    // Before overlay
-                    new_line.extend(line_chars.iter().take(start_x));
-                    // Overlay content
-                    new_line.push_str(overlay_line);
-                    // After overlay (if any)
-                    let end_x = start_x + overlay_chars.len();
-                    if end_x < line_chars.len() {
-                        new_line.extend(line_chars.iter().skip(end_x));
-                    }
-
-                    *line = new_line;
-                }
-            }
+        if let Some(recorder) = self.recorder.borrow_mut().as_mut() {
+            recorder.record(&bytes)?;
         }
+        Ok(())
+    }
 
-        result
+    /// Render board to framebuffer, with the scores above it
+    fn render_board_to_buffer(&self, board: &Board) -> FrameBuffer {
+        let style = self.style();
+        let mut court = FrameBuffer::new(board.width, board.height, ' ');
+        draw_board(&mut court, board, &style);
+        compose_screen(&court, board, &Hud::new(), &style)
     }
 
     // ============================================================================
@@ -149,60 +185,30 @@ impl GameSession {
     /// Core rendering pipeline - handles all rendering logic
     fn render_internal(
         &self,
-        board: Option<&Board>,
-        raw_content: Option<&str>,
-        overlay_lines: Option<Vec<String>>,
+        content: &str,
         clear_screen: bool,
         synchronized: bool,
     ) -> io::Result<()> {
-        let mut out = io::stdout();
+        let mut frame = Vec::with_capacity(content.len() + 16);
 
-        // Step 1: Clear screen if requested
+        // Step 1: Clear screen if requested (inside the synchronized update)
         if clear_screen {
-            queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+            queue!(frame, Clear(ClearType::All), MoveTo(0, 0))?;
         }
 
-        // Step 2: Determine base content
-        let content = if let Some(raw) = raw_content {
-            // Use provided raw content
-            raw.to_string()
-        } else if let Some(board) = board {
-            // Render board to string
-            let fb = self.render_board_to_buffer(board);
-            fb.to_string_lines()
-        } else {
-            // No content to render
-            return Ok(());
-        };
-
-        // Track if we have an overlay and apply it if needed
-        let (final_content, has_overlay) = if let Some(overlay) = overlay_lines {
-            let result = if let Some(board) = board {
-                // Apply overlay to board content
-                let base_lines: Vec<&str> = content.lines().collect();
-                let overlaid =
-                    self.apply_centered_overlay(base_lines, overlay, board.width, board.height);
-                overlaid.join("\n")
-            } else {
-                content
-            };
-            (result, true)
-        } else {
-            (content, false)
-        };
-
-        // Step 4: Convert to raw mode line endings
-        let raw_content = self.to_raw_mode(&final_content);
+        // Step 2: Convert to raw mode line endings
+        frame.extend_from_slice(self.to_raw_mode(content).as_bytes());
 
-        // Step 5: Strip trailing newline for board renders
-        let final_output = if board.is_some() || has_overlay {
-            self.strip_trailing_newline(&raw_content).as_bytes()
-        } else {
-            raw_content.as_bytes()
-        };
+        // Step 3: Write output with optional synchronization
+        self.write_output(&frame, synchronized)
+    }
 
-        // Step 6: Write output with optional synchronization
-        self.write_output(final_output, synchronized)
+    /// Present a composed frame: the single step where buffers reach the terminal.
+    fn present(&self, fb: &FrameBuffer, synchronized: bool) -> io::Result<()> {
+        // Strip the trailing newline so a full-height frame doesn't scroll
+        let lines = frame_to_ansi(fb, &self.style());
+        let content = lines.strip_suffix('\n').unwrap_or(&lines);
+        self.render_internal(content, true, synchronized)
     }
 
     // ============================================================================
@@ -211,59 +217,92 @@ impl GameSession {
 
     /// Render arbitrary content, converting line endings for raw mode.
     pub fn render(&self, content: &str) -> io::Result<()> {
-        // Simple content render: no board, no overlay, no clear, no sync
-        self.render_internal(None, Some(content), None, false, false)
+        // Simple content render: no clear, no sync
+        self.render_internal(content, false, false)
     }
 
     /// Render the game board with synchronized output.
     pub fn render_board(&self, board: &Board) -> io::Result<()> {
-        // Board render: board, no overlay, clear screen, synchronized
-        self.render_internal(Some(board), None, None, true, true)
+        let fb = self.render_board_to_buffer(board);
+        self.present(&fb, true)
+    }
+
+    /// Render a fully composed frame (board plus any layers) with synchronized output.
+    pub fn render_frame(&self, fb: &FrameBuffer) -> io::Result<()> {
+        self.present(fb, true)
     }
 
     /// Render the board with a message overlay.
     pub fn render_board_with_message(&self, board: &Board, message: &str) -> io::Result<()> {
-        // Use existing helper to render board with message
-        let rendered = render_with_message_to_string(board, message);
-        // Render the pre-composed content: no board (already rendered), clear screen, no sync
-        self.render_internal(None, Some(&rendered), None, true, false)
+        let style = self.style();
+        let mut court = FrameBuffer::new(board.width, board.height, ' ');
+        draw_board_with_message(&mut court, board, &style, message);
+        self.present(&compose_screen(&court, board, &Hud::new(), &style), false)
     }
 
     /// Render the pause menu with the game board in background.
-    pub fn render_pause_menu(&self, board: &Board) -> io::Result<()> {
-        let pause_message = "╔════════════════════════════════════════╗\n\
-                             ║              GAME PAUSED               ║\n\
-                             ╠════════════════════════════════════════╣\n\
-                             ║                                        ║\n\
-                             ║  Controls:                             ║\n\
-                             ║    W/S      - Move left paddle         ║\n\
-                             ║    ↑/↓      - Move right paddle        ║\n\
-                             ║    Space    - Pause/Resume game        ║\n\
-                             ║    Q        - Quit to main menu        ║\n\
-                             ║                                        ║\n\
-                             ║  Game Info:                            ║\n\
-                             ║    FPS: 60                             ║\n\
-                             ║    Board: 80×24                        ║\n\
-                             ║                                        ║\n\
-                             ║        Press SPACE to resume           ║\n\
-                             ║                                        ║\n\
-                             ╚════════════════════════════════════════╝";
-
-        // Convert pause message to lines, trimming leading whitespace
-        let overlay_lines: Vec<String> = pause_message
-            .lines()
-            .map(|l| l.trim_start().to_string())
-            .collect();
-
-        // Render board with pause menu overlay: board, overlay, clear screen, synchronized
-        self.render_internal(Some(board), None, Some(overlay_lines), true, true)
+    /// `fps` is the measured frame rate; everything shown comes from live state.
+    pub fn render_pause_menu(
+        &self,
+        board: &Board,
+        stats: &MatchStats,
+        fps: f64,
+        selected: usize,
+    ) -> io::Result<()> {
+        let style = self.style();
+        let panel = Panel::new("GAME PAUSED")
+            .gap(1)
+            .line("Game Info:")
+            .line(format!("  FPS: {fps:.1} (target {})", board.rules.fps))
+            .line(format!("  Board: {}×{}", board.width, board.height))
+            .line(format!(
+                "  Score: {} - {}",
+                board.score.left, board.score.right
+            ))
+            .line(format!("  Time: {}", format_clock(stats.play_time)))
+            .line(format!(
+                "  Rally: {} (longest {}, avg {:.1})",
+                board.rally,
+                stats.longest_rally,
+                stats.average_rally()
+            ))
+            .line(format!("  Theme: {}", self.theme_name()))
+            .gap(1)
+            .line(format!(
+                "Rules: {}",
+                board.rules.preset_name().unwrap_or("custom")
+            ))
+            .line(format!(
+                "  Paddles: {}/{} rows, move {}",
+                board.left.height, board.right.height, board.left.speed
+            ))
+            .line(format!(
+                "  Ball: 1 cell every {} frames",
+                board.rules.ball_speed_divisor
+            ))
+            .gap(1)
+            .with(Menu::new(&PAUSE_MENU, selected))
+            .footer(KeyHints::new(&[
+                ("Space", "Resume"),
+                ("T", "Theme"),
+                ("Q", "Main menu"),
+            ]));
+
+        // Compose the menu as a centered layer over the board, then present once
+        let mut fb = self.render_board_to_buffer(board);
+        let menu = panel.render(&style);
+        let (x, y) = fb.centered_origin(&menu);
+        let mut layers = Layers::new();
+        layers.push(1, x, y, menu);
+        layers.compose_onto(&mut fb);
+        self.present(&fb, true)
     }
 
     /// Clear the screen (accounting for raw mode).
     pub fn clear_screen(&self) -> io::Result<()> {
-        let mut out = io::stdout();
-        execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
-        out.flush()
+        let mut bytes = Vec::new();
+        queue!(bytes, Clear(ClearType::All), MoveTo(0, 0))?;
+        self.write_output(&bytes, false)
     }
 
     /// Wait for Enter key press (raw mode is already active).
@@ -271,7 +310,7 @@ impl GameSession {
         // Raw mode is already active, just read input
         loop {
             if let Event::Key(key_event) = event::read()? {
-                if key_event.code == KeyCode::Enter {
+                if key_event.code == KeyCode::Enter && key_event.kind != KeyEventKind::Release {
                     break;
                 }
             }
@@ -280,10 +319,18 @@ impl GameSession {
     }
 }
 
-impl Drop for GameSession {
+impl<W: Write> Drop for GameSession<W> {
     fn drop(&mut self) {
+        if !self.owns_terminal {
+            return;
+        }
+
         // Cleanup in reverse order, suppressing all errors
-        let mut out = io::stdout();
+        let out = self.out.get_mut();
+
+        if self.caps.kitty_keyboard {
+            let _ = execute!(out, PopKeyboardEnhancementFlags);
+        }
 
         // Show cursor
         let _ = execute!(out, Show);
@@ -298,3 +345,103 @@ impl Drop for GameSession {
         let _ = out.flush();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hud::HUD_HEIGHT;
+    use crate::terminal::ColorDepth;
+
+    const CLEAR_HOME: &str = "\x1b[2J\x1b[1;1H";
+
+    fn session(synchronized_output: bool) -> GameSession<Vec<u8>> {
+        let caps = TerminalCapabilities {
+            unicode: false,
+            colors: ColorDepth::Monochrome,
+            synchronized_output,
+            kitty_keyboard: false,
+        };
+        GameSession::with_writer(Vec::new(), caps)
+    }
+
+    fn written(session: &GameSession<Vec<u8>>) -> String {
+        String::from_utf8(session.writer().clone()).unwrap()
+    }
+
+    #[test]
+    fn render_converts_line_endings_without_clearing() {
+        let s = session(true);
+        s.render("a\nb").unwrap();
+        assert_eq!(written(&s), "a\r\nb");
+    }
+
+    #[test]
+    fn frames_are_cleared_and_wrapped_in_sync_markers() {
+        let s = session(true);
+        let mut fb = FrameBuffer::new(3, 2, ' ');
+        fb.set(1, 0, 'o');
+        s.render_frame(&fb).unwrap();
+        assert_eq!(
+            written(&s),
+            format!("{SYNC_BEGIN}{CLEAR_HOME} o \r\n   {SYNC_END}")
+        );
+    }
+
+    #[test]
+    fn sync_markers_are_skipped_when_unsupported() {
+        let s = session(false);
+        let board = Board::new_static();
+        s.render_board(&board).unwrap();
+        let out = written(&s);
+        assert!(out.starts_with(CLEAR_HOME));
+        assert!(!out.contains(SYNC_BEGIN) && !out.contains(SYNC_END));
+        assert_eq!(out.matches("\r\n").count(), board.height + HUD_HEIGHT - 1);
+    }
+
+    #[test]
+    fn recording_captures_exactly_what_was_written() {
+        let path = std::env::temp_dir().join(format!("pong_term_{}.cast", std::process::id()));
+        let s = session(true);
+        s.start_recording(CastRecorder::create(&path, 80, 24).unwrap());
+        s.render("hi\n").unwrap();
+        drop(s);
+
+        let cast = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with(r#", "o", "hi\r\n"]"#));
+    }
+
+    #[test]
+    fn pause_menu_shows_live_game_info() {
+        let s = session(false);
+        let mut board = Board::new_static();
+        board.score.left = 3;
+        board.score.right = 2;
+        let mut stats = MatchStats::new();
+        stats.play_time = std::time::Duration::from_secs(135);
+        board.rally = 1;
+        stats.record(crate::model::BallEvent::PaddleBounce, board.rally);
+        s.render_pause_menu(&board, &stats, 57.96, 0).unwrap();
+
+        let out = written(&s);
+        assert!(out.contains("FPS: 58.0 (target 60)"));
+        assert!(out.contains("Board: 80×18"));
+        assert!(out.contains("Score: 3 - 2"));
+        assert!(out.contains("Time: 02:15"));
+        assert!(out.contains("Rally: 1 (longest 1, avg 0.0)"));
+        assert!(out.contains("Rules: classic"));
+        assert!(out.contains("Paddles: 5/5 rows"));
+        // The whole panel fits on the board
+        assert!(out.contains("GAME PAUSED") && out.contains("Resume"));
+    }
+
+    #[test]
+    fn headless_session_leaves_terminal_alone() {
+        let s = session(true);
+        s.clear_screen().unwrap();
+        assert_eq!(written(&s), CLEAR_HOME);
+        drop(s); // Must not touch raw mode or the alternate screen
+    }
+}
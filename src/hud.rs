@@ -0,0 +1,126 @@
+//! Heads-up display: scoreboard font and HUD options.
+//! Drawing lives in `draw::draw_hud`; this module only holds data.
+
+/// Width of one big digit in cells.
+pub const DIGIT_WIDTH: usize = 3;
+/// Height of one big digit in cells.
+pub const DIGIT_HEIGHT: usize = 5;
+/// Empty columns between big digits.
+pub const DIGIT_SPACING: usize = 1;
+/// Rows the HUD takes above the court: the big digits, then a line for the
+/// status and rally.
+pub const HUD_HEIGHT: usize = DIGIT_HEIGHT + 1;
+
+/// 3×5 block digits; '#' marks a filled cell.
+const DIGITS: [[&str; DIGIT_HEIGHT]; 10] = [
+    ["###", "# #", "# #", "# #", "###"],
+    [" # ", "## ", " # ", " # ", "###"],
+    ["###", "  #", "###", "#  ", "###"],
+    ["###", "  #", " ##", "  #", "###"],
+    ["# #", "# #", "###", "  #", "  #"],
+    ["###", "#  ", "###", "  #", "###"],
+    ["###", "#  ", "###", "# #", "###"],
+    ["###", "  #", "  #", " # ", " # "],
+    ["###", "# #", "###", "# #", "###"],
+    ["###", "# #", "###", "  #", "###"],
+];
+
+/// Rows of the big glyph for `digit` (0-9).
+pub fn digit_rows(digit: u32) -> [&'static str; DIGIT_HEIGHT] {
+    DIGITS[(digit % 10) as usize]
+}
+
+/// Width in cells of `value` drawn with big digits.
+pub fn big_number_width(value: u32) -> usize {
+    let digits = value.to_string().len();
+    digits * DIGIT_WIDTH + (digits - 1) * DIGIT_SPACING
+}
+
+/// Filled cells of `value` in big digits, as (x, y) offsets from its top-left.
+pub fn big_number_cells(value: u32) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    for (i, digit) in value.to_string().chars().enumerate() {
+        let x = i * (DIGIT_WIDTH + DIGIT_SPACING);
+        let rows = digit_rows(digit.to_digit(10).unwrap_or(0));
+        for (row, pattern) in rows.iter().enumerate() {
+            for (col, cell) in pattern.chars().enumerate() {
+                if cell == '#' {
+                    cells.push((x + col, row));
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// Optional HUD content beyond the scores themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hud {
+    pub left_name: Option<String>,
+    pub right_name: Option<String>,
+    /// A line under the scores, e.g. games and sets.
+    pub status: Option<String>,
+    pub show_rally: bool,
+}
+
+impl Hud {
+    /// Scores plus rally counter, no player names.
+    pub fn new() -> Self {
+        Self {
+            left_name: None,
+            right_name: None,
+            status: None,
+            show_rally: true,
+        }
+    }
+
+    /// Set both player names.
+    pub fn with_names(mut self, left: &str, right: &str) -> Self {
+        self.left_name = Some(left.to_string());
+        self.right_name = Some(right.to_string());
+        self
+    }
+
+    /// Set the line shown under the scores.
+    pub fn with_status(mut self, status: &str) -> Self {
+        self.status = Some(status.to_string());
+        self
+    }
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_font_is_uniform() {
+        for d in 0..10 {
+            for row in digit_rows(d) {
+                assert_eq!(row.len(), DIGIT_WIDTH);
+            }
+        }
+    }
+
+    #[test]
+    fn big_number_widths() {
+        assert_eq!(big_number_width(0), 3);
+        assert_eq!(big_number_width(7), 3);
+        assert_eq!(big_number_width(10), 7);
+        assert_eq!(big_number_width(123), 11);
+    }
+
+    #[test]
+    fn big_number_cells_offset_each_digit() {
+        // "1" is 8 cells; the second digit starts one spacing column later
+        let cells = big_number_cells(11);
+        assert_eq!(cells.len(), 16);
+        assert!(cells.contains(&(1, 0)));
+        assert!(cells.contains(&(DIGIT_WIDTH + DIGIT_SPACING + 1, 0)));
+    }
+}
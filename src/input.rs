@@ -1,15 +1,17 @@
 //! Cross-platform terminal input handling using crossterm with momentum-based movement.
 //! This avoids key repeat delay issues by implementing movement momentum.
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
-use std::cell::RefCell;
+use crate::rules::GameRules;
+use crate::settings::{Action, KeyBindings};
+use crate::terminal::TerminalCapabilities;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use std::cell::{Cell, RefCell};
 use std::io;
 use std::time::Duration;
 
-/// Maximum momentum frames per key press.
+/// Momentum frames per key press until `configure_input` sets the rules' value.
 /// This determines how long paddles continue moving after a key press.
-/// Higher values = longer movement continuation.
-const MAX_MOMENTUM: u8 = 5;
+const DEFAULT_MOMENTUM: u8 = GameRules::CLASSIC.momentum;
 
 /// Input state containing all active inputs for this frame.
 /// Uses momentum to handle smooth movement without key repeat delays.
@@ -21,6 +23,13 @@ pub struct InputState {
     pub right_down: bool,
     pub quit: bool,
     pub pause: bool,
+    /// Save an HTML/SVG snapshot of the current frame.
+    pub screenshot: bool,
+    /// Show or hide the debug overlay.
+    pub toggle_debug: bool,
+    /// Launch a held serve.
+    pub left_serve: bool,
+    pub right_serve: bool,
 }
 
 impl InputState {
@@ -37,52 +46,123 @@ impl InputState {
             || self.right_down
             || self.quit
             || self.pause
+            || self.screenshot
+            || self.toggle_debug
+            || self.left_serve
+            || self.right_serve
     }
 }
 
+/// A discrete key press on a menu screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Enter: activate the selected item.
+    Select,
+    /// Esc or Q: leave the screen.
+    Back,
+    /// Space: resume from the pause menu.
+    Pause,
+    /// Any other character, lowercased (menu shortcuts such as `t`).
+    Char(char),
+}
+
 /// Momentum tracker for smooth paddle movement.
 /// When a key is pressed, momentum is set and gradually decreases.
+/// Terminals that report key releases also mark keys as held until released.
 struct MomentumTracker {
     left_up_momentum: u8,
     left_down_momentum: u8,
     right_up_momentum: u8,
     right_down_momentum: u8,
+    left_up_held: bool,
+    left_down_held: bool,
+    right_up_held: bool,
+    right_down_held: bool,
+    /// Momentum given by a key press.
+    max_momentum: u8,
 }
 
 impl MomentumTracker {
-    fn new() -> Self {
+    fn new(max_momentum: u8) -> Self {
         Self {
             left_up_momentum: 0,
             left_down_momentum: 0,
             right_up_momentum: 0,
             right_down_momentum: 0,
+            left_up_held: false,
+            left_down_held: false,
+            right_up_held: false,
+            right_down_held: false,
+            max_momentum,
+        }
+    }
+
+    /// Mark a movement as held or released. Releasing stops it at once.
+    fn set_held(&mut self, action: Action, held: bool) {
+        match action {
+            Action::LeftUp => {
+                self.left_up_held = held;
+                self.left_down_held &= !held;
+            }
+            Action::LeftDown => {
+                self.left_down_held = held;
+                self.left_up_held &= !held;
+            }
+            Action::RightUp => {
+                self.right_up_held = held;
+                self.right_down_held &= !held;
+            }
+            Action::RightDown => {
+                self.right_down_held = held;
+                self.right_up_held &= !held;
+            }
+            // Serves don't move paddles
+            Action::LeftServe | Action::RightServe => return,
+        }
+        if held {
+            self.add_momentum(action);
+        } else {
+            self.clear_momentum(action);
+        }
+    }
+
+    fn clear_momentum(&mut self, action: Action) {
+        match action {
+            Action::LeftUp => self.left_up_momentum = 0,
+            Action::LeftDown => self.left_down_momentum = 0,
+            Action::RightUp => self.right_up_momentum = 0,
+            Action::RightDown => self.right_down_momentum = 0,
+            Action::LeftServe | Action::RightServe => {}
         }
     }
 
     /// Add momentum when a key is pressed.
     /// This resets the momentum to max value (smooth continuous movement).
-    fn add_momentum(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char('w') | KeyCode::Char('W') => {
-                self.left_up_momentum = MAX_MOMENTUM;
+    fn add_momentum(&mut self, action: Action) {
+        match action {
+            Action::LeftUp => {
+                self.left_up_momentum = self.max_momentum;
                 self.left_down_momentum = 0; // Cancel opposite direction
             }
-            KeyCode::Char('s') | KeyCode::Char('S') => {
-                self.left_down_momentum = MAX_MOMENTUM;
+            Action::LeftDown => {
+                self.left_down_momentum = self.max_momentum;
                 self.left_up_momentum = 0; // Cancel opposite direction
             }
-            KeyCode::Up => {
-                self.right_up_momentum = MAX_MOMENTUM;
+            Action::RightUp => {
+                self.right_up_momentum = self.max_momentum;
                 self.right_down_momentum = 0; // Cancel opposite direction
             }
-            KeyCode::Down => {
-                self.right_down_momentum = MAX_MOMENTUM;
+            Action::RightDown => {
+                self.right_down_momentum = self.max_momentum;
                 self.right_up_momentum = 0; // Cancel opposite direction
             }
-            _ => {}
+            Action::LeftServe | Action::RightServe => {}
         }
     }
-
     /// Decay momentum over time and return current input state.
     fn get_state(&mut self) -> (bool, bool, bool, bool) {
         // Decay momentum by 1 each frame
@@ -100,25 +180,43 @@ impl MomentumTracker {
         }
 
         (
-            self.left_up_momentum > 0,
-            self.left_down_momentum > 0,
-            self.right_up_momentum > 0,
-            self.right_down_momentum > 0,
+            self.left_up_momentum > 0 || self.left_up_held,
+            self.left_down_momentum > 0 || self.left_down_held,
+            self.right_up_momentum > 0 || self.right_up_held,
+            self.right_down_momentum > 0 || self.right_down_held,
         )
     }
 }
 
 // Thread-local momentum tracker for safe access
 thread_local! {
-    static MOMENTUM: RefCell<MomentumTracker> = RefCell::new(MomentumTracker::new());
+    static MOMENTUM: RefCell<MomentumTracker> = RefCell::new(MomentumTracker::new(DEFAULT_MOMENTUM));
+    static KEY_BINDINGS: Cell<KeyBindings> = Cell::new(KeyBindings::default());
+    static MOMENTUM_FRAMES: Cell<u8> = const { Cell::new(DEFAULT_MOMENTUM) };
     static LAST_PAUSE_STATE: RefCell<bool> = const { RefCell::new(false) };
+    static RELEASE_EVENTS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Initialize input handling for a session's terminal.
+/// With key release events (kitty protocol) paddles move while keys are held.
+pub fn init_input(caps: &TerminalCapabilities) {
+    init_momentum();
+    RELEASE_EVENTS.with(|r| r.set(caps.kitty_keyboard));
+}
+
+/// Use these paddle keys and momentum from now on. Resets the momentum tracker.
+pub fn configure_input(keys: KeyBindings, momentum: u8) {
+    KEY_BINDINGS.with(|k| k.set(keys));
+    MOMENTUM_FRAMES.with(|m| m.set(momentum));
+    init_momentum();
 }
 
 /// Initialize the momentum tracker for smooth input handling.
 /// This resets the momentum tracker to initial state.
 pub fn init_momentum() {
+    let max_momentum = MOMENTUM_FRAMES.with(Cell::get);
     MOMENTUM.with(|m| {
-        *m.borrow_mut() = MomentumTracker::new();
+        *m.borrow_mut() = MomentumTracker::new(max_momentum);
     });
     LAST_PAUSE_STATE.with(|p| {
         *p.borrow_mut() = false;
@@ -145,11 +243,45 @@ pub fn poll_input() -> io::Result<InputState> {
     // Process all pending key events
     let mut pause_pressed = false;
     let mut quit_pressed = false;
+    let mut screenshot_pressed = false;
+    let mut debug_pressed = false;
+    let mut left_serve = false;
+    let mut right_serve = false;
     let mut space_key_seen = false;
 
+    let release_events = RELEASE_EVENTS.with(Cell::get);
+    let keys = KEY_BINDINGS.with(Cell::get);
+
     // Poll for events with zero timeout (non-blocking)
     while event::poll(Duration::ZERO)? {
-        if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+        if let Event::Key(KeyEvent { code, kind, .. }) = event::read()? {
+            let movement = keys.action_for(code);
+            // Serve keys act once per press and never add momentum
+            if let Some(serve @ (Action::LeftServe | Action::RightServe)) = movement {
+                if kind == KeyEventKind::Press {
+                    left_serve |= serve == Action::LeftServe;
+                    right_serve |= serve == Action::RightServe;
+                }
+                continue;
+            }
+            if let (true, Some(action)) = (release_events, movement) {
+                MOMENTUM.with(|m| {
+                    m.borrow_mut()
+                        .set_held(action, kind != KeyEventKind::Release);
+                });
+                continue;
+            }
+            // Only presses trigger actions; repeats and releases are ignored
+            if kind != KeyEventKind::Press {
+                continue;
+            }
+            // Movement keys add momentum
+            if let Some(action) = movement {
+                MOMENTUM.with(|m| {
+                    m.borrow_mut().add_momentum(action);
+                });
+                continue;
+            }
             match code {
                 KeyCode::Char(' ') => {
                     space_key_seen = true;
@@ -165,16 +297,11 @@ pub fn poll_input() -> io::Result<InputState> {
                 KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
                     quit_pressed = true;
                 }
-                // Movement keys add momentum
-                KeyCode::Char('w')
-                | KeyCode::Char('W')
-                | KeyCode::Char('s')
-                | KeyCode::Char('S')
-                | KeyCode::Up
-                | KeyCode::Down => {
-                    MOMENTUM.with(|m| {
-                        m.borrow_mut().add_momentum(code);
-                    });
+                KeyCode::F(2) => {
+                    screenshot_pressed = true;
+                }
+                KeyCode::F(3) => {
+                    debug_pressed = true;
                 }
                 _ => {}
             }
@@ -198,5 +325,103 @@ pub fn poll_input() -> io::Result<InputState> {
         right_down,
         quit: quit_pressed,
         pause: pause_pressed,
+        screenshot: screenshot_pressed,
+        toggle_debug: debug_pressed,
+        left_serve,
+        right_serve,
     })
 }
+
+/// Poll for menu key presses without blocking.
+/// Space is reported once per hold, like the pause key in `poll_input`, so
+/// the press that opened a menu can't immediately close it.
+pub fn poll_menu() -> io::Result<Vec<MenuKey>> {
+    let mut keys = Vec::new();
+    let mut space_key_seen = false;
+
+    while event::poll(Duration::ZERO)? {
+        let Event::Key(KeyEvent { code, kind, .. }) = event::read()? else {
+            continue;
+        };
+        if kind == KeyEventKind::Release {
+            continue;
+        }
+        let key = match code {
+            KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => MenuKey::Up,
+            KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('S') => MenuKey::Down,
+            KeyCode::Left => MenuKey::Left,
+            KeyCode::Right => MenuKey::Right,
+            KeyCode::Enter => MenuKey::Select,
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') | KeyCode::Char('Q') => {
+                MenuKey::Back
+            }
+            KeyCode::Char(' ') => {
+                space_key_seen = true;
+                let held = LAST_PAUSE_STATE.with(|state| state.replace(true));
+                if held {
+                    continue;
+                }
+                MenuKey::Pause
+            }
+            KeyCode::Char(c) => MenuKey::Char(c.to_ascii_lowercase()),
+            _ => continue,
+        };
+        keys.push(key);
+    }
+
+    if !space_key_seen {
+        LAST_PAUSE_STATE.with(|state| {
+            *state.borrow_mut() = false;
+        });
+    }
+    Ok(keys)
+}
+
+/// Poll for raw key presses without blocking, for capturing a key binding.
+pub fn poll_key_presses() -> io::Result<Vec<KeyCode>> {
+    let mut keys = Vec::new();
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        {
+            keys.push(code);
+        }
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_keys_move_until_released() {
+        let mut tracker = MomentumTracker::new(DEFAULT_MOMENTUM);
+        tracker.set_held(Action::RightUp, true);
+        // Held well past the momentum window
+        for _ in 0..DEFAULT_MOMENTUM * 2 {
+            assert_eq!(tracker.get_state(), (false, false, true, false));
+        }
+        tracker.set_held(Action::RightUp, false);
+        assert_eq!(tracker.get_state(), (false, false, false, false));
+    }
+
+    #[test]
+    fn pressing_the_opposite_key_cancels_the_held_one() {
+        let mut tracker = MomentumTracker::new(DEFAULT_MOMENTUM);
+        tracker.set_held(Action::LeftUp, true);
+        tracker.set_held(Action::LeftDown, true);
+        assert_eq!(tracker.get_state(), (false, true, false, false));
+    }
+
+    #[test]
+    fn momentum_lasts_the_configured_frames() {
+        let mut tracker = MomentumTracker::new(3);
+        tracker.add_momentum(Action::LeftDown);
+        let moving: Vec<bool> = (0..4).map(|_| tracker.get_state().1).collect();
+        assert_eq!(moving, vec![true, true, false, false]);
+    }
+}
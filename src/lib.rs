@@ -1,31 +1,70 @@
-//! Terminal Pong - Stage 3: evented terminal input and game loop.
+//! Terminal Pong: two-player, squash and breakout Pong in the terminal.
 //!
 //! Crate layout:
-//! - model: data structures with mutable paddle movement
+//! - app: screen stack (menus, match, pause, game over) and the main loop
+//! - model: board, paddles and ball physics, sets, clock, serves and bricks
 //! - framebuffer: 2D character buffer for building frames
 //! - draw: pure drawing functions (model -> framebuffer)
-//! - render: ANSI terminal output with synchronized updates
+//! - export: HTML and SVG snapshots of a frame
+//! - debug: frame timing stats and the debug overlay
+//! - effects: transient cosmetic effects (ball trail, flashes, sparks)
+//! - hud: scoreboard font and HUD options
+//! - render: frames to plain strings or ANSI-colored output
+//! - recorder: asciicast v2 recording of session output
 //! - terminal: terminal utilities and capability detection
+//! - theme: built-in and user-loadable glyph/color themes
+//! - config: config directory and `key = value` file parsing
+//! - cli: command-line option parsing
+//! - records: personal records (best squash run) saved in the config directory
+//! - rules: game rules (physics and input tuning) with presets
+//! - settings: player settings saved in the config directory
+//! - stats: match statistics (rallies, play time)
 //! - input: raw mode terminal input handling
-//! - game_loop: main game loop with fixed frame rate
-//! - game_session: unified terminal session management
+//! - game_loop: a match per game mode, serve sequence and fixed-rate loop
+//! - game_session: terminal session with synchronized updates and recording
+//! - widgets: panel, menu, label and progress bar widgets for dialogs
 //! - util: utility functions (char/string width calculations)
 
+pub mod app;
+pub mod cli;
+pub mod config;
+pub mod debug;
 pub mod draw;
+pub mod effects;
+pub mod export;
 pub mod framebuffer;
 pub mod game_loop;
 pub mod game_session;
+pub mod hud;
 pub mod input;
 pub mod model;
+pub mod recorder;
+pub mod records;
 pub mod render;
+pub mod rules;
+pub mod settings;
+pub mod stats;
 pub mod terminal;
+pub mod theme;
 pub mod util;
+pub mod widgets;
 
+pub use app::{run_app, App, GameMode, Screen};
+pub use cli::CliOptions;
 pub use draw::draw_board_with_message;
-pub use framebuffer::FrameBuffer;
-pub use game_loop::run_game_loop;
+pub use effects::Effects;
+pub use framebuffer::{FrameBuffer, Layers};
+pub use game_loop::Match;
 pub use game_session::GameSession;
-pub use input::{wait_for_enter_no_echo, InputState};
-pub use model::{Ball, BallEvent, Board, Paddle, HEIGHT, PADDLE_HEIGHT, WIDTH};
+pub use hud::Hud;
+pub use input::{wait_for_enter_no_echo, InputState, MenuKey};
+pub use model::{Ball, BallEvent, Board, Paddle, PaddleHitLocation, Score, Side, HEIGHT, WIDTH};
+pub use recorder::CastRecorder;
 pub use render::{render_to_string, render_with_message_to_string};
-pub use terminal::{print_setup_instructions, RenderStyle};
+pub use rules::GameRules;
+pub use settings::{KeyBindings, Settings};
+pub use stats::MatchStats;
+pub use terminal::{
+    print_setup_instructions, ColorDepth, Palette, RenderStyle, TerminalCapabilities,
+};
+pub use theme::Theme;
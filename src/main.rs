@@ -1,29 +1,110 @@
-use pong_term::{print_setup_instructions, run_game_loop, Board, GameSession, HEIGHT, WIDTH};
+use pong_term::cli::USAGE;
+use pong_term::hud::HUD_HEIGHT;
+use pong_term::{
+    print_setup_instructions, run_app, App, CastRecorder, CliOptions, GameRules, GameSession,
+    Settings, Theme, HEIGHT, WIDTH,
+};
+use std::process;
 
 fn main() -> std::io::Result<()> {
-    // Print setup instructions (in normal screen)
-    print_setup_instructions(WIDTH, HEIGHT)?;
+    let options = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    if options.help {
+        print!("{USAGE}");
+        return Ok(());
+    }
 
-    // Enter game session (alternate screen + raw mode + hide cursor)
-    let session = GameSession::enter()?;
+    // Saved settings; problems are warnings and fall back to the defaults
+    let settings_path = Settings::path();
+    let mut settings = Settings::default();
+    if let Some(path) = &settings_path {
+        let (loaded, errors) = Settings::load_from(path);
+        for err in errors {
+            eprintln!("warning: {}: {err}", path.display());
+        }
+        settings = loaded;
+    }
 
-    // Display the static board with controls inside
-    let board = Board::new_static();
+    // `--rules` replaces the saved rules for this run only; rules edited on
+    // the settings screen are saved as usual
+    let rules = match &options.rules {
+        Some(name) => match GameRules::load(name) {
+            Ok(rules) => Some(rules),
+            Err(errors) => {
+                for err in errors {
+                    eprintln!("rules `{name}`: {err}");
+                }
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
 
-    // Render the board with controls message (shorter to fit within borders)
-    session.render_board_with_message(
-        &board,
-        "W/S: Left | ↑/↓: Right | Space: Pause | Enter: Start",
-    )?;
+    // Load the theme up front so errors are shown in the normal screen.
+    // `--theme` overrides the saved theme for this run.
+    let theme = match options.theme.as_deref().map(Theme::load).transpose() {
+        Ok(Some(theme)) => Some(theme),
+        Ok(None) => match Theme::load(&settings.theme) {
+            Ok(theme) => Some(theme),
+            Err(errors) => {
+                for err in errors {
+                    eprintln!("warning: saved theme `{}`: {err}", settings.theme);
+                }
+                settings.theme = Settings::default().theme;
+                None
+            }
+        },
+        Err(errors) => {
+            let name = options.theme.unwrap_or_default();
+            for err in errors {
+                eprintln!("theme `{name}`: {err}");
+            }
+            process::exit(1);
+        }
+    };
 
-    // Wait for user to press Enter to start the game
-    session.wait_for_enter()?;
+    // Create the recording before the game screen so errors stay readable
+    let recorder = match &options.record {
+        Some(path) => {
+            let (width, height) =
+                crossterm::terminal::size().unwrap_or((WIDTH as u16, (HEIGHT + HUD_HEIGHT) as u16));
+            match CastRecorder::create(path, width, height) {
+                Ok(recorder) => Some(recorder),
+                Err(err) => {
+                    eprintln!("cannot record to {}: {err}", path.display());
+                    process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
 
-    // Clear screen before starting game
-    session.clear_screen()?;
+    // Print setup instructions (in normal screen)
+    print_setup_instructions(WIDTH, HEIGHT + HUD_HEIGHT)?;
+
+    // Enter game session (alternate screen + raw mode + hide cursor)
+    let session = GameSession::enter()?;
+    if let Some(theme) = &theme {
+        session.set_theme(theme);
+    }
+    if let Some(recorder) = recorder {
+        session.start_recording(recorder);
+    }
 
-    // Run the game loop (will handle its own cleanup)
-    let game_result = run_game_loop(&session);
+    // Menus and matches until the player quits from the main menu
+    let mut app = App::new(settings, settings_path);
+    if let Some(rules) = rules {
+        app.override_rules(rules);
+    }
+    if options.theme.is_some() {
+        app.override_theme(session.theme_name());
+    }
+    let game_result = run_app(&session, app);
 
     // GameSession Drop will handle cleanup (exit alternate screen, restore terminal)
     drop(session);
@@ -1,40 +1,89 @@
 //! Game model: Board, Paddle, Ball.
-//! Stage 4: Added ball physics and collision detection.
+//! Ball physics and collisions, scoring with sets, match clock and held
+//! serves, handicaps, and the squash back wall and breakout bricks.
+
+use crate::rules::{GameRules, Handicap, ASSIST_RANGE};
+use std::time::Duration;
 
 pub const WIDTH: usize = 80;
-pub const HEIGHT: usize = 24;
-pub const PADDLE_HEIGHT: usize = 5;
-pub const PADDLE_SPEED: usize = 1; // How many cells paddle moves per update
-pub const BALL_SPEED_DIVISOR: usize = 2; // Ball moves every N frames (higher = slower)
+/// Court height; the HUD takes the rows above it, so the screen is
+/// `HEIGHT + hud::HUD_HEIGHT` rows.
+pub const HEIGHT: usize = 18;
+
+/// Levels in breakout, each with another column of bricks.
+pub const BREAKOUT_LEVELS: u32 = 3;
+
+/// An axis-aligned block of cells the ball can run into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    /// Whether the cell at (x, y) lies inside.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Paddle {
     pub x: usize,
     pub y: usize,
     pub height: usize,
+    pub speed: usize, // Cells moved per update
 }
 
 impl Paddle {
-    /// Move paddle up by PADDLE_SPEED, respecting board boundaries.
+    /// Cells the paddle covers.
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            width: 1,
+            height: self.height,
+        }
+    }
+
+    /// Move paddle up by its speed, respecting board boundaries.
     pub fn move_up(&mut self) {
         // Ensure paddle doesn't go above the top border (y=1)
-        if self.y > 1 + PADDLE_SPEED {
-            self.y -= PADDLE_SPEED;
+        if self.y > 1 + self.speed {
+            self.y -= self.speed;
         } else if self.y > 1 {
             self.y = 1;
         }
     }
 
-    /// Move paddle down by PADDLE_SPEED, respecting board boundaries.
+    /// Move paddle down by its speed, respecting board boundaries.
     pub fn move_down(&mut self, board_height: usize) {
         // Ensure paddle doesn't go below the bottom border
         let max_y = board_height.saturating_sub(self.height + 1);
-        if self.y + PADDLE_SPEED < max_y {
-            self.y += PADDLE_SPEED;
+        if self.y + self.speed < max_y {
+            self.y += self.speed;
         } else if self.y < max_y {
             self.y = max_y;
         }
     }
+
+    /// Hit zone for a ball at row `y` (rows above the paddle count as its top).
+    pub fn hit_location(&self, y: usize) -> PaddleHitLocation {
+        let relative_y = y.saturating_sub(self.y);
+
+        // Split the paddle into 5 zones (one row each on a 5-row paddle)
+        // 0 = top edge, 4 = bottom edge
+        let zone = relative_y * 5 / self.height.max(1);
+        match zone {
+            0 => PaddleHitLocation::TopEdge,    // Very top
+            1 => PaddleHitLocation::TopMid,     // Upper area
+            2 => PaddleHitLocation::Center,     // Center
+            3 => PaddleHitLocation::BottomMid,  // Lower area
+            _ => PaddleHitLocation::BottomEdge, // Very bottom (4+)
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -70,19 +119,6 @@ impl Ball {
     pub fn bounce_vertical(&mut self) {
         self.dy = -self.dy;
     }
-
-    /// Reset ball to center with specified direction.
-    /// towards_left: true means ball goes left (after right player scores)
-    pub fn reset(&mut self, board_width: usize, board_height: usize, towards_left: bool) {
-        self.x = board_width / 2;
-        self.y = board_height / 2;
-
-        // Direction away from scorer
-        self.dx = if towards_left { -1 } else { 1 };
-
-        // Start with straight horizontal movement, can randomize later
-        self.dy = 0;
-    }
 }
 
 /// Events that can occur during ball physics updates.
@@ -91,13 +127,37 @@ pub enum BallEvent {
     None,
     WallBounce,
     PaddleBounce,
+    BrickHit,
     LeftGoal,  // Right player scores
     RightGoal, // Left player scores
 }
 
+/// One of the two players, by the side of the court they defend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Side::Left => "Left",
+            Side::Right => "Right",
+        }
+    }
+}
+
 /// Where on the paddle the ball hit.
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum PaddleHitLocation {
+pub enum PaddleHitLocation {
     TopEdge,    // Very top - strong upward angle
     TopMid,     // Upper area - moderate upward angle
     Center,     // Center area - straight
@@ -105,6 +165,206 @@ enum PaddleHitLocation {
     BottomEdge, // Very bottom - strong downward angle
 }
 
+impl PaddleHitLocation {
+    /// All zones from top to bottom.
+    pub const ALL: [PaddleHitLocation; 5] = [
+        PaddleHitLocation::TopEdge,
+        PaddleHitLocation::TopMid,
+        PaddleHitLocation::Center,
+        PaddleHitLocation::BottomMid,
+        PaddleHitLocation::BottomEdge,
+    ];
+
+    /// Position in `ALL`, 0 at the top.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PaddleHitLocation::TopEdge => "Top edge",
+            PaddleHitLocation::TopMid => "Upper",
+            PaddleHitLocation::Center => "Center",
+            PaddleHitLocation::BottomMid => "Lower",
+            PaddleHitLocation::BottomEdge => "Bottom edge",
+        }
+    }
+}
+
+/// Points scored by each player.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Score {
+    pub left: u32,
+    pub right: u32,
+}
+
+impl Score {
+    pub fn get(&self, side: Side) -> u32 {
+        match side {
+            Side::Left => self.left,
+            Side::Right => self.right,
+        }
+    }
+
+    fn add(&mut self, side: Side) {
+        match side {
+            Side::Left => self.left += 1,
+            Side::Right => self.right += 1,
+        }
+    }
+
+    /// The same score seen from the other end.
+    pub fn swapped(self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+        }
+    }
+}
+
+/// Games and sets of a match played in sets. A game is won at the points
+/// target, a set by the first to `games_per_set` games, and the match by a
+/// majority of `best_of` sets. Tallies are kept by side, like the score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetScore {
+    pub best_of: u32,
+    pub games_per_set: u32,
+    /// Games won in the set in progress.
+    pub games: Score,
+    pub sets: Score,
+    /// Final games of each finished set, in order.
+    pub finished: Vec<Score>,
+}
+
+impl SetScore {
+    pub fn new(best_of: u32, games_per_set: u32) -> Self {
+        Self {
+            best_of,
+            games_per_set,
+            games: Score::default(),
+            sets: Score::default(),
+            finished: Vec::new(),
+        }
+    }
+
+    /// Sets needed to win the match.
+    pub fn sets_to_win(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Number of the set in progress from 1, or of the last set once the
+    /// match is over.
+    pub fn current_set(&self) -> usize {
+        self.finished.len() + usize::from(self.winner().is_none())
+    }
+
+    /// Award a game to `side`, closing the set when it has enough games.
+    /// Returns true if that won the match.
+    pub fn win_game(&mut self, side: Side) -> bool {
+        self.games.add(side);
+        if self.games.get(side) >= self.games_per_set {
+            self.sets.add(side);
+            self.finished.push(self.games);
+            self.games = Score::default();
+        }
+        self.winner().is_some()
+    }
+
+    /// The side that has won the match, if any.
+    pub fn winner(&self) -> Option<Side> {
+        let needed = self.sets_to_win();
+        if self.sets.left >= needed {
+            Some(Side::Left)
+        } else if self.sets.right >= needed {
+            Some(Side::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Players changed ends: every tally follows its player.
+    pub fn swap_sides(&mut self) {
+        self.games = self.games.swapped();
+        self.sets = self.sets.swapped();
+        for set in &mut self.finished {
+            *set = set.swapped();
+        }
+    }
+}
+
+/// A serve waiting to be played: the server carries the ball on their paddle
+/// until they launch it, or the timeout launches it for them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Serve {
+    pub side: Side,
+    /// Ticks until the auto-serve.
+    pub ticks_left: u32,
+}
+
+/// Countdown of a timed match in simulation ticks, so pauses and slow
+/// frames never change the outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchClock {
+    pub ticks_left: u32,
+    /// Time ran out on a tie: the next goal wins.
+    pub overtime: bool,
+}
+
+impl MatchClock {
+    pub fn new(seconds: u32, fps: u32) -> Self {
+        Self {
+            ticks_left: seconds * fps,
+            overtime: false,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.ticks_left == 0
+    }
+
+    /// Time left at `fps`, rounded up to whole seconds.
+    pub fn remaining(&self, fps: u32) -> Duration {
+        Duration::from_secs(self.ticks_left.div_ceil(fps.max(1)).into())
+    }
+}
+
+/// Breakout: columns of bricks in front of the back wall, one more column
+/// each level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakout {
+    pub level: u32,
+    pub bricks: Vec<Rect>,
+}
+
+impl Breakout {
+    /// Lay out `level` on a court of the given size: level + 1 columns of
+    /// bricks stacked down the right side, keeping clear of the back wall.
+    pub fn new(level: u32, width: usize, height: usize) -> Self {
+        let mut bricks = Vec::new();
+        for column in 0..=level as usize {
+            let Some(x) = width.checked_sub(6 + column * 4) else {
+                break;
+            };
+            let mut y = 2;
+            while y + 3 < height - 1 {
+                bricks.push(Rect {
+                    x,
+                    y,
+                    width: 2,
+                    height: 3,
+                });
+                y += 4;
+            }
+        }
+        Self { level, bricks }
+    }
+
+    /// Every brick of the last level is gone.
+    pub fn cleared(&self) -> bool {
+        self.bricks.is_empty() && self.level >= BREAKOUT_LEVELS
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Board {
     pub width: usize,
@@ -113,24 +373,47 @@ pub struct Board {
     pub right: Paddle,
     pub ball: Ball,
     pub frame_counter: usize, // Track frames for ball speed control
+    pub score: Score,
+    pub rally: usize, // Paddle hits since the last serve
+    pub rules: GameRules,
+    pub last_hit: Option<(Side, PaddleHitLocation)>, // Most recent paddle hit
+    pub sets: Option<SetScore>, // Games and sets, when the match is played in sets
+    pub clock: Option<MatchClock>, // Time left, when the match is timed
+    pub serve: Option<Serve>,   // Ball held for a serve
+    pub left_handicap: Handicap,
+    pub right_handicap: Handicap,
+    /// Squash practice: the right edge is a wall instead of a goal, with no
+    /// right paddle. The left score is the current run of returns and the
+    /// right score the best run.
+    pub right_wall: bool,
+    /// Bricks to clear in front of the back wall. The left score counts
+    /// bricks broken and the right score missed balls instead.
+    pub breakout: Option<Breakout>,
 }
 
 impl Board {
     /// Create a static board with paddles and ball at initial positions.
     pub fn new_static() -> Self {
-        let paddle_y = (HEIGHT - PADDLE_HEIGHT) / 2;
+        Self::with_rules(GameRules::CLASSIC)
+    }
+
+    /// A static board sized and tuned by `rules`.
+    pub fn with_rules(rules: GameRules) -> Self {
+        let paddle_y = (HEIGHT - rules.paddle_height) / 2;
         Board {
             width: WIDTH,
             height: HEIGHT,
             left: Paddle {
                 x: 1,
                 y: paddle_y,
-                height: PADDLE_HEIGHT,
+                height: rules.paddle_height,
+                speed: rules.paddle_speed,
             },
             right: Paddle {
                 x: WIDTH - 2,
                 y: paddle_y,
-                height: PADDLE_HEIGHT,
+                height: rules.paddle_height,
+                speed: rules.paddle_speed,
             },
             ball: Ball {
                 x: WIDTH / 2,
@@ -139,20 +422,167 @@ impl Board {
                 dy: 0,
             },
             frame_counter: 0,
+            score: Score::default(),
+            rally: 0,
+            rules,
+            last_hit: None,
+            sets: None,
+            clock: None,
+            serve: None,
+            left_handicap: Handicap::default(),
+            right_handicap: Handicap::default(),
+            right_wall: false,
+            breakout: None,
         }
     }
 
-    /// Create a new game board with ball velocity for active gameplay.
-    pub fn new_game() -> Self {
-        let mut board = Self::new_static();
-        // Set initial ball velocity - start towards right with slight upward angle
-        board.ball.dx = 1;
-        board.ball.dy = -1;
+    /// Create a new game board with the left player holding the opening serve.
+    pub fn new_game(rules: GameRules) -> Self {
+        let mut board = Self::with_rules(rules);
+        board.start_serve(Side::Left);
         board
     }
 
+    /// Hand the ball to `side` for a serve.
+    pub fn start_serve(&mut self, side: Side) {
+        self.serve = Some(Serve {
+            side,
+            ticks_left: self.rules.auto_serve_seconds * self.rules.fps,
+        });
+        self.hold_ball();
+    }
+
+    /// Keep a held ball against the middle of the server's paddle.
+    pub fn hold_ball(&mut self) {
+        let Some(serve) = self.serve else {
+            return;
+        };
+        let (paddle, x) = match serve.side {
+            Side::Left => (&self.left, self.left.x + 1),
+            Side::Right => (&self.right, self.right.x - 1),
+        };
+        self.ball = Ball {
+            x,
+            y: paddle.y + paddle.height / 2,
+            dx: 0,
+            dy: 0,
+        };
+    }
+
+    /// Launch the ball if `side` is holding it. Returns true if it was.
+    pub fn launch_serve(&mut self, side: Side) -> bool {
+        if self.serve.map(|serve| serve.side) != Some(side) {
+            return false;
+        }
+        self.serve = None;
+        self.ball.dx = match side {
+            Side::Left => 1,
+            Side::Right => -1,
+        };
+        self.ball.dy = 0;
+        self.frame_counter = 0;
+        true
+    }
+
+    /// Award a point to `scorer` and hand the next serve over by the rules.
+    fn point_to(&mut self, scorer: Side) {
+        match scorer {
+            Side::Left => self.score.left += 1,
+            Side::Right => self.score.right += 1,
+        }
+        self.frame_counter = 0;
+        self.rally = 0;
+        let played = self.score.left + self.score.right;
+        self.start_serve(self.rules.serve.server(scorer, played));
+    }
+
+    /// Switch rules mid-game: paddles take the new size and speed and are
+    /// kept inside the court.
+    pub fn set_rules(&mut self, rules: GameRules) {
+        // The clock keeps the same time left at the new frame rate
+        if let Some(clock) = &mut self.clock {
+            clock.ticks_left = (clock.ticks_left * rules.fps).div_ceil(self.rules.fps.max(1));
+        }
+        self.rules = rules;
+        self.fit_paddles();
+    }
+
+    /// Give each side its handicap and resize the paddles to match.
+    pub fn set_handicaps(&mut self, left: Handicap, right: Handicap) {
+        self.left_handicap = left;
+        self.right_handicap = right;
+        self.fit_paddles();
+    }
+
+    /// Size paddles from the rules and handicaps, keeping them in the court.
+    fn fit_paddles(&mut self) {
+        let sides = [
+            (&mut self.left, self.left_handicap),
+            (&mut self.right, self.right_handicap),
+        ];
+        for (paddle, handicap) in sides {
+            (paddle.height, paddle.speed) = handicap.paddle(&self.rules);
+            paddle.y = paddle.y.min(self.height.saturating_sub(paddle.height + 1));
+        }
+    }
+
+    /// Steer assisted paddles one cell towards a ball heading their way.
+    /// The strongest assist moves every tick, weaker ones less often.
+    pub fn assist_paddles(&mut self) {
+        let ball = self.ball.clone();
+        let tick = self.frame_counter;
+        let max_y = |paddle: &Paddle| self.height.saturating_sub(paddle.height + 1);
+        let sides = [
+            (&mut self.left, self.left_handicap.assist, ball.dx < 0),
+            (
+                &mut self.right,
+                self.right_handicap.assist,
+                ball.dx > 0 && !self.right_wall,
+            ),
+        ];
+        for (paddle, assist, incoming) in sides {
+            let every = (ASSIST_RANGE.1 + 1).saturating_sub(assist).max(1) as usize;
+            if assist == 0 || !incoming || tick % every != 0 {
+                continue;
+            }
+            let center = paddle.y + paddle.height / 2;
+            if ball.y < center && paddle.y > 1 {
+                paddle.y -= 1;
+            } else if ball.y > center && paddle.y < max_y(paddle) {
+                paddle.y += 1;
+            }
+        }
+    }
+
+    /// Count one tick of play off the match clock, if there is one. Time
+    /// running out on a tied score starts overtime.
+    pub fn tick_clock(&mut self) {
+        let Some(clock) = &mut self.clock else {
+            return;
+        };
+        if clock.ticks_left > 0 {
+            clock.ticks_left -= 1;
+            if clock.ticks_left == 0 && self.score.left == self.score.right {
+                clock.overtime = true;
+            }
+        }
+    }
+
     /// Update ball physics - returns event for feedback.
     pub fn update_ball(&mut self) -> BallEvent {
+        // A held ball follows the paddle until it's served
+        if let Some(serve) = self.serve {
+            if serve.ticks_left > 0 {
+                self.serve = Some(Serve {
+                    ticks_left: serve.ticks_left - 1,
+                    ..serve
+                });
+                self.hold_ball();
+                return BallEvent::None;
+            }
+            self.launch_serve(serve.side);
+        }
+
         // Adjust speed based on angle - angled balls move slightly faster
         let speed_divisor = if self.ball.dy != 0 {
             // Ball is angled - move every 3 frames out of 4 (faster)
@@ -161,10 +591,10 @@ impl Board {
                 self.frame_counter += 1;
                 return BallEvent::None;
             }
-            BALL_SPEED_DIVISOR
+            self.rules.ball_speed_divisor.max(1)
         } else {
             // Ball is straight - normal speed
-            BALL_SPEED_DIVISOR
+            self.rules.ball_speed_divisor.max(1)
         };
 
         // Only move ball based on speed divisor
@@ -177,8 +607,9 @@ impl Board {
         self.ball.update_position();
 
         // 1. Check paddle collisions FIRST (priority)
-        if let Some(paddle_hit) = self.check_paddle_collision_with_angle() {
+        if let Some((side, paddle_hit)) = self.check_paddle_collision_with_angle() {
             self.ball.bounce_horizontal();
+            self.last_hit = Some((side, paddle_hit));
 
             // Apply angle based on where ball hit the paddle
             match paddle_hit {
@@ -199,48 +630,68 @@ impl Board {
                 }
             }
 
+            self.rally += 1;
+            if self.right_wall && self.breakout.is_none() {
+                self.score.left += 1;
+                self.score.right = self.score.right.max(self.score.left);
+            }
             return BallEvent::PaddleBounce;
         }
 
-        // 2. Check wall collisions
+        // 2. Check bricks
+        if self.check_brick_collision() {
+            return BallEvent::BrickHit;
+        }
+
+        // 3. Check wall collisions
         if self.check_wall_collision() {
             self.ball.bounce_vertical();
             return BallEvent::WallBounce;
         }
+        if self.check_back_wall_collision() {
+            self.ball.bounce_horizontal();
+            return BallEvent::WallBounce;
+        }
 
-        // 3. Check for goals
+        // 4. Check for goals
         if self.ball.x == 0 {
-            // Left goal - right player scores
-            self.ball.reset(self.width, self.height, true); // Ball goes left
-            self.frame_counter = 0; // Reset frame counter
+            if self.breakout.is_some() {
+                // Missed ball - counted, and served again
+                self.score.right += 1;
+                self.frame_counter = 0;
+                self.rally = 0;
+                self.start_serve(Side::Left);
+            } else if self.right_wall {
+                // Missed return - the run starts over with a new serve
+                self.score.left = 0;
+                self.frame_counter = 0;
+                self.rally = 0;
+                self.start_serve(Side::Left);
+            } else {
+                // Left goal - right player scores
+                self.point_to(Side::Right);
+            }
             return BallEvent::LeftGoal;
         }
         if self.ball.x >= self.width - 1 {
             // Right goal - left player scores
-            self.ball.reset(self.width, self.height, false); // Ball goes right
-            self.frame_counter = 0; // Reset frame counter
+            self.point_to(Side::Left);
             return BallEvent::RightGoal;
         }
 
         BallEvent::None
     }
 
-    /// Check if ball collides with either paddle and return hit location.
-    fn check_paddle_collision_with_angle(&self) -> Option<PaddleHitLocation> {
+    /// Check if ball collides with either paddle and return which one and where.
+    fn check_paddle_collision_with_angle(&self) -> Option<(Side, PaddleHitLocation)> {
         // Left paddle collision
-        if self.ball.x == self.left.x
-            && self.ball.y >= self.left.y
-            && self.ball.y < self.left.y + self.left.height
-        {
-            return Some(self.get_paddle_hit_location(&self.left));
+        if self.left.rect().contains(self.ball.x, self.ball.y) {
+            return Some((Side::Left, self.get_paddle_hit_location(&self.left)));
         }
 
-        // Right paddle collision
-        if self.ball.x == self.right.x
-            && self.ball.y >= self.right.y
-            && self.ball.y < self.right.y + self.right.height
-        {
-            return Some(self.get_paddle_hit_location(&self.right));
+        // Right paddle collision (there is none in front of a back wall)
+        if !self.right_wall && self.right.rect().contains(self.ball.x, self.ball.y) {
+            return Some((Side::Right, self.get_paddle_hit_location(&self.right)));
         }
 
         None
@@ -248,37 +699,48 @@ impl Board {
 
     /// Determine where on the paddle the ball hit.
     fn get_paddle_hit_location(&self, paddle: &Paddle) -> PaddleHitLocation {
-        let relative_y = self.ball.y.saturating_sub(paddle.y);
-
-        // Paddle is 5 units tall (0-4 relative positions)
-        // 0 = top edge, 4 = bottom edge
-        match relative_y {
-            0 => PaddleHitLocation::TopEdge,    // Very top
-            1 => PaddleHitLocation::TopMid,     // Upper area
-            2 => PaddleHitLocation::Center,     // Center
-            3 => PaddleHitLocation::BottomMid,  // Lower area
-            _ => PaddleHitLocation::BottomEdge, // Very bottom (4+)
-        }
+        paddle.hit_location(self.ball.y)
     }
 
     /// Check if ball collides with either paddle.
     #[cfg(test)]
     fn check_paddle_collision(&self) -> bool {
-        // Left paddle collision
-        if self.ball.x == self.left.x {
-            if self.ball.y >= self.left.y && self.ball.y < self.left.y + self.left.height {
-                return true;
-            }
-        }
+        let (x, y) = (self.ball.x, self.ball.y);
+        // There is no right paddle in front of a back wall
+        self.left.rect().contains(x, y) || (!self.right_wall && self.right.rect().contains(x, y))
+    }
 
-        // Right paddle collision
-        if self.ball.x == self.right.x {
-            if self.ball.y >= self.right.y && self.ball.y < self.right.y + self.right.height {
-                return true;
-            }
+    /// Break a brick the ball ran into and bounce off it: back the way it
+    /// came when it entered through a side, off the top or bottom otherwise.
+    /// Clearing a level sets up the next one with a fresh serve.
+    fn check_brick_collision(&mut self) -> bool {
+        let Some(breakout) = &mut self.breakout else {
+            return false;
+        };
+        let ball = &mut self.ball;
+        let Some(index) = breakout
+            .bricks
+            .iter()
+            .position(|brick| brick.contains(ball.x, ball.y))
+        else {
+            return false;
+        };
+        let brick = breakout.bricks.remove(index);
+        let prev_x = (ball.x as i32 - ball.dx as i32) as usize;
+        if brick.contains(prev_x, ball.y) {
+            ball.bounce_vertical();
+        } else {
+            ball.bounce_horizontal();
         }
+        self.score.left += 1;
 
-        false
+        if breakout.bricks.is_empty() && breakout.level < BREAKOUT_LEVELS {
+            *breakout = Breakout::new(breakout.level + 1, self.width, self.height);
+            self.frame_counter = 0;
+            self.rally = 0;
+            self.start_serve(Side::Left);
+        }
+        true
     }
 
     /// Check if ball hits top or bottom wall.
@@ -296,6 +758,11 @@ impl Board {
         false
     }
 
+    /// Check if the ball reaches the back wall that replaces the right goal.
+    fn check_back_wall_collision(&self) -> bool {
+        self.right_wall && self.ball.x >= self.right.x && self.ball.dx > 0
+    }
+
     /// Move left paddle up.
     pub fn move_left_paddle_up(&mut self) {
         self.left.move_up();
@@ -318,9 +785,37 @@ impl Board {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// Put the ball one step short of `side`'s goal, above the paddles, so
+    /// the next move is a miss on that side.
+    pub(crate) fn ball_before_goal(board: &mut Board, side: Side) {
+        let (x, dx) = match side {
+            Side::Left => (1, -1),
+            Side::Right => (board.width - 2, 1),
+        };
+        board.serve = None;
+        board.ball = Ball { x, y: 5, dx, dy: 0 };
+        board.frame_counter = 1;
+    }
+
+    /// Put the ball one step from the centre of `side`'s paddle, heading in.
+    pub(crate) fn ball_before_paddle(board: &mut Board, side: Side) {
+        let (x, dx, paddle) = match side {
+            Side::Left => (board.left.x + 1, -1, &board.left),
+            Side::Right => (board.right.x - 1, 1, &board.right),
+        };
+        board.serve = None;
+        board.ball = Ball {
+            x,
+            y: paddle.y + 2,
+            dx,
+            dy: 0,
+        };
+        board.frame_counter = 1;
+    }
+
     #[test]
     fn paddle_movement_constraints() {
         let mut board = Board::new_static();
@@ -335,7 +830,7 @@ mod tests {
         for _ in 0..30 {
             board.move_left_paddle_down();
         }
-        assert_eq!(board.left.y, HEIGHT - PADDLE_HEIGHT - 1); // Should stop at bottom border
+        assert_eq!(board.left.y, HEIGHT - board.left.height - 1); // Should stop at bottom border
 
         // Test right paddle similarly
         for _ in 0..20 {
@@ -344,6 +839,28 @@ mod tests {
         assert_eq!(board.right.y, 1);
     }
 
+    #[test]
+    fn rules_set_paddle_size_and_speeds() {
+        let mut board = Board::new_static();
+        board.left.y = HEIGHT - 6;
+        board.set_rules(GameRules {
+            paddle_height: 8,
+            paddle_speed: 3,
+            ball_speed_divisor: 1,
+            ..GameRules::CLASSIC
+        });
+        assert_eq!((board.left.height, board.left.y), (8, HEIGHT - 9));
+        let y = board.left.y;
+        board.move_left_paddle_up();
+        assert_eq!(board.left.y, y - 3);
+
+        // Divisor 1 moves the ball every frame
+        board.ball.dx = 1;
+        let x = board.ball.x;
+        board.update_ball();
+        assert_eq!(board.ball.x, x + 1);
+    }
+
     #[test]
     fn test_ball_movement() {
         let mut ball = Ball {
@@ -431,9 +948,13 @@ mod tests {
         board.frame_counter = 1; // Ensure ball moves on next update
         let event = board.update_ball();
         assert_eq!(event, BallEvent::LeftGoal);
-        assert_eq!(board.ball.x, WIDTH / 2); // Ball reset to center
+        assert_eq!(board.score, Score { left: 0, right: 1 });
+        // The loser holds the ball for the next serve
+        assert_eq!(board.serve.map(|serve| serve.side), Some(Side::Left));
+        assert_eq!(board.ball.x, board.left.x + 1);
 
         // Test right goal - position ball just before the goal
+        board.serve = None;
         board.ball.x = WIDTH - 2;
         board.ball.dx = 1;
         board.ball.dy = 0; // No vertical movement
@@ -441,7 +962,26 @@ mod tests {
         board.frame_counter = 1; // Ensure ball moves on next update
         let event = board.update_ball();
         assert_eq!(event, BallEvent::RightGoal);
-        assert_eq!(board.ball.x, WIDTH / 2);
+        assert_eq!(board.ball.x, board.right.x - 1);
+        assert_eq!(board.score, Score { left: 1, right: 1 });
+    }
+
+    #[test]
+    fn test_rally_counts_paddle_hits_until_goal() {
+        let mut board = Board::new_static();
+
+        ball_before_paddle(&mut board, Side::Left);
+        assert_eq!(board.update_ball(), BallEvent::PaddleBounce);
+        assert_eq!(board.rally, 1);
+        assert_eq!(
+            board.last_hit,
+            Some((Side::Left, PaddleHitLocation::Center))
+        );
+
+        // Rally resets once a point is scored
+        ball_before_goal(&mut board, Side::Left);
+        assert_eq!(board.update_ball(), BallEvent::LeftGoal);
+        assert_eq!(board.rally, 0);
     }
 
     #[test]
@@ -454,6 +994,7 @@ mod tests {
             x: 1,
             y: 10,
             height: 5,
+            speed: 1,
         };
 
         // Position 0: Top edge (y=10)
@@ -480,6 +1021,16 @@ mod tests {
         test_board.ball.y = 14;
         let location = test_board.get_paddle_hit_location(&paddle);
         assert_eq!(location, PaddleHitLocation::BottomEdge);
+
+        // Taller paddles spread the same five zones over more rows
+        let tall = Paddle {
+            height: 10,
+            ..paddle
+        };
+        let zones: Vec<PaddleHitLocation> = (10..20).map(|y| tall.hit_location(y)).collect();
+        assert_eq!(zones[0..2], [PaddleHitLocation::TopEdge; 2]);
+        assert_eq!(zones[4..6], [PaddleHitLocation::Center; 2]);
+        assert_eq!(zones[8..10], [PaddleHitLocation::BottomEdge; 2]);
     }
 
     #[test]
@@ -500,4 +1051,225 @@ mod tests {
         assert_eq!(board.ball.x, 11); // Ball moved
         assert_eq!(board.frame_counter, 2);
     }
+
+    #[test]
+    fn test_sets_are_won_by_games() {
+        let mut sets = SetScore::new(3, 2);
+        assert_eq!(sets.sets_to_win(), 2);
+        assert!(!sets.win_game(Side::Left));
+        assert!(!sets.win_game(Side::Left));
+        assert_eq!(sets.sets, Score { left: 1, right: 0 });
+        assert_eq!(sets.games, Score::default());
+        assert_eq!(sets.current_set(), 2);
+
+        // Ends change: the leader's set moves to the right with them
+        sets.swap_sides();
+        sets.win_game(Side::Left);
+        assert_eq!(sets.games, Score { left: 1, right: 0 });
+        assert_eq!(sets.finished, vec![Score { left: 0, right: 2 }]);
+        assert!(!sets.win_game(Side::Right));
+        assert!(sets.win_game(Side::Right));
+        assert_eq!(sets.winner(), Some(Side::Right));
+    }
+
+    #[test]
+    fn test_clock_runs_out_into_overtime_on_a_tie() {
+        let mut board = Board::with_rules(GameRules {
+            fps: 4,
+            ..GameRules::CLASSIC
+        });
+        board.clock = Some(MatchClock::new(1, 4));
+        for _ in 0..3 {
+            board.tick_clock();
+        }
+        let clock = board.clock.unwrap();
+        assert_eq!(clock.remaining(4), Duration::from_secs(1));
+        assert!(!clock.expired());
+
+        // Changing the frame rate keeps the time left
+        board.set_rules(GameRules {
+            fps: 2,
+            ..GameRules::CLASSIC
+        });
+        board.set_rules(GameRules::CLASSIC);
+        assert_eq!(board.clock.unwrap().remaining(60), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            board.tick_clock();
+        }
+        let clock = board.clock.unwrap();
+        assert!(clock.expired() && clock.overtime);
+    }
+
+    #[test]
+    fn test_held_serve_follows_the_paddle_until_launched() {
+        let mut board = Board::new_game(GameRules {
+            auto_serve_seconds: 1,
+            fps: 2,
+            ..GameRules::CLASSIC
+        });
+        board.move_left_paddle_up();
+        assert_eq!(board.update_ball(), BallEvent::None);
+        assert_eq!(board.ball.y, board.left.y + board.left.height / 2);
+
+        // Only the server can launch
+        assert!(!board.launch_serve(Side::Right));
+        assert!(board.launch_serve(Side::Left));
+        assert_eq!((board.ball.dx, board.ball.dy), (1, 0));
+        assert_eq!(board.serve, None);
+
+        // Left alone, the serve goes after the timeout
+        board.start_serve(Side::Right);
+        board.update_ball();
+        board.update_ball();
+        assert!(board.serve.is_some());
+        board.update_ball();
+        assert_eq!(board.serve, None);
+        assert_eq!(board.ball.dx, -1);
+    }
+
+    #[test]
+    fn test_handicaps_resize_paddles_and_assist_steers() {
+        let mut board = Board::new_static();
+        let assisted = Handicap {
+            paddle_height: 2,
+            assist: 3,
+            ..Handicap::default()
+        };
+        board.set_handicaps(assisted, Handicap::default());
+        assert_eq!((board.left.height, board.right.height), (7, 5));
+
+        // Rules changes keep the handicap on top
+        board.set_rules(GameRules::ARCADE);
+        assert_eq!((board.left.height, board.right.height), (9, 7));
+
+        // Only the paddle the ball is heading for is steered
+        let (left_y, right_y) = (board.left.y, board.right.y);
+        board.ball.y = 2;
+        board.ball.dx = -1;
+        board.assist_paddles();
+        assert_eq!(board.left.y, left_y - 1);
+        board.ball.dx = 1;
+        board.assist_paddles();
+        assert_eq!((board.left.y, board.right.y), (left_y - 1, right_y));
+    }
+
+    #[test]
+    fn test_back_wall_returns_the_ball_and_runs_are_counted() {
+        let mut board = Board::new_static();
+        board.right_wall = true;
+        board.score.right = 1; // Best run so far
+
+        // The right goal is a wall, even where the right paddle would be
+        ball_before_paddle(&mut board, Side::Right);
+        assert_eq!(board.update_ball(), BallEvent::WallBounce);
+        assert_eq!(board.ball.dx, -1);
+
+        // Two returns beat the best run
+        for _ in 0..2 {
+            ball_before_paddle(&mut board, Side::Left);
+            assert_eq!(board.update_ball(), BallEvent::PaddleBounce);
+        }
+        assert_eq!(board.score, Score { left: 2, right: 2 });
+
+        // A miss ends the run but keeps the best
+        ball_before_goal(&mut board, Side::Left);
+        assert_eq!(board.update_ball(), BallEvent::LeftGoal);
+        assert_eq!(board.score, Score { left: 0, right: 2 });
+        assert_eq!(board.serve.map(|serve| serve.side), Some(Side::Left));
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let rect = Rect {
+            x: 2,
+            y: 3,
+            width: 2,
+            height: 3,
+        };
+        assert!(rect.contains(2, 3) && rect.contains(3, 5));
+        assert!(!rect.contains(4, 3) && !rect.contains(2, 6) && !rect.contains(1, 4));
+    }
+
+    #[test]
+    fn test_breakout_levels_add_a_column_of_bricks() {
+        let first = Breakout::new(1, WIDTH, HEIGHT);
+        let last = Breakout::new(BREAKOUT_LEVELS, WIDTH, HEIGHT);
+        assert_eq!(first.bricks.len(), 6);
+        assert_eq!(last.bricks.len(), 12);
+
+        // Bricks stay apart, inside the court and off the back wall
+        let court = Rect {
+            x: 2,
+            y: 1,
+            width: WIDTH - 4,
+            height: HEIGHT - 2,
+        };
+        for (i, brick) in last.bricks.iter().enumerate() {
+            assert!(court.contains(brick.x, brick.y));
+            assert!(court.contains(brick.x + brick.width - 1, brick.y + brick.height - 1));
+            for other in &last.bricks[i + 1..] {
+                let overlaps = (other.y..other.y + other.height)
+                    .any(|y| (other.x..other.x + other.width).any(|x| brick.contains(x, y)));
+                assert!(!overlaps);
+            }
+        }
+        assert!(!last.cleared());
+        assert!(Breakout {
+            bricks: Vec::new(),
+            ..last
+        }
+        .cleared());
+    }
+
+    #[test]
+    fn test_bricks_break_and_bounce_until_the_level_is_cleared() {
+        let mut board = Board::new_static();
+        board.right_wall = true;
+        let brick = Rect {
+            x: 50,
+            y: 10,
+            width: 2,
+            height: 3,
+        };
+        board.breakout = Some(Breakout {
+            level: 1,
+            bricks: vec![brick, Rect { y: 14, ..brick }],
+        });
+
+        // Into the side of a brick: back the way it came
+        board.ball = Ball {
+            x: 49,
+            y: 11,
+            dx: 1,
+            dy: 0,
+        };
+        board.frame_counter = 1;
+        assert_eq!(board.update_ball(), BallEvent::BrickHit);
+        assert_eq!((board.ball.dx, board.ball.dy), (-1, 0));
+        assert_eq!(board.breakout.as_ref().unwrap().bricks.len(), 1);
+
+        // Onto the top of the last one: off it, and on to the next level
+        board.ball = Ball {
+            x: 50,
+            y: 13,
+            dx: 1,
+            dy: 1,
+        };
+        board.frame_counter = 1;
+        assert_eq!(board.update_ball(), BallEvent::BrickHit);
+        assert_eq!(board.score.left, 2);
+        let breakout = board.breakout.as_ref().unwrap();
+        assert_eq!(breakout.level, 2);
+        assert_eq!(breakout.bricks.len(), 9);
+        assert_eq!(board.serve.map(|serve| serve.side), Some(Side::Left));
+
+        // Paddle returns don't score; a miss is counted and served again
+        ball_before_paddle(&mut board, Side::Left);
+        assert_eq!(board.update_ball(), BallEvent::PaddleBounce);
+        ball_before_goal(&mut board, Side::Left);
+        assert_eq!(board.update_ball(), BallEvent::LeftGoal);
+        assert_eq!(board.score, Score { left: 2, right: 1 });
+        assert_eq!(board.serve.map(|serve| serve.side), Some(Side::Left));
+    }
 }
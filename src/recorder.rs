@@ -0,0 +1,124 @@
+//! Session recording in asciicast v2 format, playable with `asciinema play`.
+//!
+//! The file is one JSON header line followed by one `[time, "o", data]`
+//! event per write. The JSON is written by hand to keep dependencies minimal.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Records every byte written to the terminal, with timestamps.
+pub struct CastRecorder<R: Write = BufWriter<File>> {
+    out: R,
+    start: Instant,
+}
+
+impl CastRecorder {
+    /// Create `path` and write the header for a `width`×`height` terminal.
+    pub fn create(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::new(
+            BufWriter::new(File::create(path)?),
+            width,
+            height,
+            timestamp,
+        )
+    }
+}
+
+impl<R: Write> CastRecorder<R> {
+    /// Start a recording on `out`; `timestamp` is the Unix start time.
+    pub fn new(mut out: R, width: u16, height: u16, timestamp: u64) -> io::Result<Self> {
+        let mut header = format!(
+            r#"{{"version": 2, "width": {width}, "height": {height}, "timestamp": {timestamp}"#
+        );
+        if let Ok(term) = std::env::var("TERM") {
+            header.push_str(r#", "env": {"TERM": "#);
+            push_json_string(&mut header, &term);
+            header.push('}');
+        }
+        header.push_str("}\n");
+        out.write_all(header.as_bytes())?;
+
+        Ok(Self {
+            out,
+            start: Instant::now(),
+        })
+    }
+
+    /// Append an output event for `data`, timed from the start of the recording.
+    pub fn record(&mut self, data: &[u8]) -> io::Result<()> {
+        self.record_at(self.start.elapsed(), data)
+    }
+
+    fn record_at(&mut self, time: Duration, data: &[u8]) -> io::Result<()> {
+        let mut event = format!("[{:.6}, \"o\", ", time.as_secs_f64());
+        // Frames are built from Strings, so lossy conversion never kicks in
+        push_json_string(&mut event, &String::from_utf8_lossy(data));
+        event.push_str("]\n");
+        self.out.write_all(event.as_bytes())?;
+        self.out.flush()
+    }
+
+    /// The underlying writer.
+    pub fn into_inner(self) -> R {
+        self.out
+    }
+}
+
+/// Append `s` as a quoted JSON string.
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(s: &str) -> String {
+        let mut out = String::new();
+        push_json_string(&mut out, s);
+        out
+    }
+
+    #[test]
+    fn json_strings_escape_quotes_and_control_characters() {
+        assert_eq!(json("plain │●"), r#""plain │●""#);
+        assert_eq!(json("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(json("\x1b[2J\r\n"), r#""\u001b[2J\r\n""#);
+    }
+
+    #[test]
+    fn recording_has_header_then_timed_events() {
+        let mut rec = CastRecorder::new(Vec::new(), 80, 24, 1_700_000_000).unwrap();
+        rec.record_at(Duration::from_millis(1500), b"\x1b[?2026hhi")
+            .unwrap();
+        let text = String::from_utf8(rec.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0]
+            .starts_with(r#"{"version": 2, "width": 80, "height": 24, "timestamp": 1700000000"#));
+        assert!(lines[0].ends_with('}'));
+        assert_eq!(lines[1], r#"[1.500000, "o", "\u001b[?2026hhi"]"#);
+        assert_eq!(lines.len(), 2);
+    }
+}
@@ -0,0 +1,91 @@
+//! Personal records kept between runs as `records.conf` next to the settings,
+//! in the same `key = value` format. For now that's the best squash run.
+
+use crate::config::{self, ConfigError};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// File name inside the config directory.
+pub const RECORDS_FILE: &str = "records.conf";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Records {
+    /// Most consecutive returns in squash practice.
+    pub squash_best: u32,
+}
+
+impl Records {
+    /// Load records from `path`. A missing file means no records yet.
+    pub fn load_from(path: &Path) -> (Records, Vec<ConfigError>) {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (Records::default(), Vec::new()),
+            Err(err) => (
+                Records::default(),
+                vec![ConfigError::new(0, format!("{}: {err}", path.display()))],
+            ),
+        }
+    }
+
+    /// Parse a records file; bad lines are reported and skipped.
+    pub fn parse(text: &str) -> (Records, Vec<ConfigError>) {
+        let (entries, mut errors) = config::parse_entries(text);
+        let mut records = Records::default();
+        for entry in entries {
+            match (entry.key.as_str(), entry.value.parse()) {
+                ("squash_best", Ok(best)) => records.squash_best = best,
+                ("squash_best", Err(_)) => errors.push(ConfigError::new(
+                    entry.line,
+                    format!("expected a number, got `{}`", entry.value),
+                )),
+                (key, _) => errors.push(ConfigError::new(
+                    entry.line,
+                    format!("unknown record `{key}`"),
+                )),
+            }
+        }
+        errors.sort_by_key(|e| e.line);
+        (records, errors)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Terminal Pong personal records\n");
+        let _ = writeln!(text, "squash_best = {}", self.squash_best);
+        text
+    }
+
+    /// Write the records to `path`, creating its directory if needed.
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_round_trips() {
+        let records = Records { squash_best: 42 };
+        assert_eq!(Records::parse(&records.to_text()), (records, Vec::new()));
+    }
+
+    #[test]
+    fn bad_lines_are_reported() {
+        let (records, errors) = Records::parse("squash_best = lots\nbreakout = 3\n");
+        assert_eq!(records, Records::default());
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "line 1: expected a number, got `lots`",
+                "line 2: unknown record `breakout`",
+            ]
+        );
+    }
+}
@@ -2,14 +2,23 @@
 //! Output and terminal management is handled by GameSession.
 
 use crate::draw::{draw_board, draw_board_with_message};
-use crate::framebuffer::FrameBuffer;
+use crate::framebuffer::{FrameBuffer, TRANSPARENT, WIDE_CONTINUATION};
 use crate::model::Board;
 use crate::terminal::RenderStyle;
+use crossterm::style::{Color, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::Command;
+use std::sync::OnceLock;
+
+/// Style for the string renderers, detected from the environment once.
+fn auto_style() -> RenderStyle {
+    static STYLE: OnceLock<RenderStyle> = OnceLock::new();
+    *STYLE.get_or_init(RenderStyle::auto)
+}
 
 /// Render the board to a String including clear + home, then the frame.
 /// Uses auto-detected render style (Unicode if supported, ASCII fallback).
 pub fn render_to_string(board: &Board) -> String {
-    let style = RenderStyle::auto();
+    let style = auto_style();
     let mut fb = FrameBuffer::new(board.width, board.height, ' ');
     draw_board(&mut fb, board, &style);
 
@@ -26,7 +35,7 @@ pub fn render_to_string(board: &Board) -> String {
 
 /// Render the board with a message inside, to a String.
 pub fn render_with_message_to_string(board: &Board, message: &str) -> String {
-    let style = RenderStyle::auto();
+    let style = auto_style();
     let mut fb = FrameBuffer::new(board.width, board.height, ' ');
     draw_board_with_message(&mut fb, board, &style, message);
 
@@ -39,12 +48,49 @@ pub fn render_with_message_to_string(board: &Board, message: &str) -> String {
     }
 }
 
+/// Convert a frame to newline-terminated lines with ANSI colors from the
+/// style's palette. Without any colors this is exactly `to_string_lines`.
+pub fn frame_to_ansi(fb: &FrameBuffer, style: &RenderStyle) -> String {
+    if style.colors.is_empty() {
+        return fb.to_string_lines();
+    }
+
+    let mut out = String::with_capacity(fb.width() * fb.height() * 4);
+    for y in 0..fb.height() {
+        if let Some(bg) = style.colors.background {
+            // Writing into a String cannot fail
+            let _ = SetBackgroundColor(bg).write_ansi(&mut out);
+        }
+        let mut current: Option<Color> = None;
+        for x in 0..fb.width() {
+            let ch = match fb.get(x, y) {
+                Some(WIDE_CONTINUATION) | None => continue,
+                Some(TRANSPARENT) => ' ',
+                Some(ch) => ch,
+            };
+            // Spaces keep whatever color is active to avoid needless escapes
+            let color = fb.element(x, y).and_then(|e| style.colors.color(e));
+            if ch != ' ' && color != current {
+                let _ = SetForegroundColor(color.unwrap_or(Color::Reset)).write_ansi(&mut out);
+                current = color;
+            }
+            out.push(ch);
+        }
+        // Reset at end of line so colors never bleed past the frame
+        let _ = ResetColor.write_ansi(&mut out);
+        out.push('\n');
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::framebuffer::Element;
     use crate::model::{Board, HEIGHT, WIDTH};
 
     #[test]
+    #[allow(clippy::needless_range_loop, clippy::chars_next_cmp)]
     fn top_and_bottom_borders_are_correct_length_and_chars() {
         let b = Board::new_static();
         let s = render_to_string(&b);
@@ -74,14 +120,17 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn paddles_and_ball_positions() {
         let b = Board::new_static();
         let s = render_to_string(&b);
         let lines: Vec<Vec<char>> = s.lines().map(|ln| ln.chars().collect()).collect();
 
-        // Check ball (could be 'o' or '●' depending on style)
+        // Check ball (could be 'o', '●' or a braille dot depending on style)
         let ball_char = lines[b.ball.y][b.ball.x];
-        assert!(ball_char == 'o' || ball_char == '●');
+        assert!(
+            ball_char == 'o' || ball_char == '●' || ('\u{2800}'..='\u{28ff}').contains(&ball_char)
+        );
 
         // Check left paddle (could be '|' or '█')
         for y in b.left.y..(b.left.y + b.left.height) {
@@ -99,4 +148,30 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn ansi_frame_without_colors_is_plain_text() {
+        let b = Board::new_static();
+        let style = RenderStyle::ascii();
+        let mut fb = FrameBuffer::new(b.width, b.height, ' ');
+        draw_board(&mut fb, &b, &style);
+        assert_eq!(frame_to_ansi(&fb, &style), fb.to_string_lines());
+    }
+
+    #[test]
+    fn ansi_frame_colors_runs_and_resets_each_line() {
+        let mut style = RenderStyle::ascii();
+        style.colors.border = Some(Color::Blue);
+        style.colors.ball = Some(Color::Yellow);
+        let mut fb = FrameBuffer::new(5, 1, ' ');
+        fb.set_as(0, 0, '-', Element::Border);
+        fb.set_as(1, 0, '-', Element::Border);
+        fb.set_as(3, 0, 'o', Element::Ball);
+        // Text takes the text color even when it looks like the ball
+        fb.set(4, 0, 'o');
+        assert_eq!(
+            frame_to_ansi(&fb, &style),
+            "\x1b[38;5;12m-- \x1b[38;5;11mo\x1b[39mo\x1b[0m\n"
+        );
+    }
 }
@@ -0,0 +1,425 @@
+//! Game rules: physics and input tuning in one value, with named presets and
+//! a small `key = value` text form so house rules can be shared as a file.
+
+use crate::config::{self, ConfigError};
+use crate::model::Side;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Frame rates offered on the settings screen.
+pub const FPS_CHOICES: [u32; 5] = [30, 45, 60, 90, 120];
+
+/// Allowed ranges for the numeric rules; the settings screen clamps to them
+/// and the parser rejects values outside them.
+pub const PADDLE_HEIGHT_RANGE: (usize, usize) = (2, 10);
+pub const PADDLE_SPEED_RANGE: (usize, usize) = (1, 4);
+pub const BALL_SPEED_RANGE: (usize, usize) = (1, 6);
+pub const POINTS_RANGE: (u32, u32) = (1, 21);
+pub const MOMENTUM_RANGE: (u8, u8) = (1, 15);
+pub const GAMES_PER_SET_RANGE: (u32, u32) = (1, 6);
+pub const AUTO_SERVE_RANGE: (u32, u32) = (1, 10);
+
+/// Handicap ranges: offsets to the paddle rules, a head start in points
+/// and how strongly the paddle is steered towards the ball.
+pub const HANDICAP_HEIGHT_RANGE: (i32, i32) = (-3, 3);
+pub const HANDICAP_SPEED_RANGE: (i32, i32) = (-2, 2);
+pub const START_SCORE_RANGE: (u32, u32) = (0, 10);
+pub const ASSIST_RANGE: (u32, u32) = (0, 3);
+
+/// Per-player adjustments that even out a match between players of
+/// different skill. The default is no handicap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Handicap {
+    /// Cells added to the paddle height from the rules.
+    pub paddle_height: i32,
+    /// Added to the paddle speed from the rules.
+    pub paddle_speed: i32,
+    /// Points on the board when each game starts.
+    pub start_score: u32,
+    /// 0 is off; each level steers the paddle towards an incoming ball more often.
+    pub assist: u32,
+}
+
+impl Handicap {
+    /// Paddle height and speed under `rules`, kept inside the rule ranges.
+    pub fn paddle(&self, rules: &GameRules) -> (usize, usize) {
+        let adjust = |value: usize, offset: i32, (min, max): (usize, usize)| {
+            (value as i32 + offset).clamp(min as i32, max as i32) as usize
+        };
+        (
+            adjust(rules.paddle_height, self.paddle_height, PADDLE_HEIGHT_RANGE),
+            adjust(rules.paddle_speed, self.paddle_speed, PADDLE_SPEED_RANGE),
+        )
+    }
+
+    /// Compact form for tables, e.g. `H+1 S-1 P3 A2`; `-` for none.
+    pub fn summary(&self) -> String {
+        if *self == Self::default() {
+            return "-".to_string();
+        }
+        let mut parts = Vec::new();
+        if self.paddle_height != 0 {
+            parts.push(format!("H{:+}", self.paddle_height));
+        }
+        if self.paddle_speed != 0 {
+            parts.push(format!("S{:+}", self.paddle_speed));
+        }
+        if self.start_score != 0 {
+            parts.push(format!("P{}", self.start_score));
+        }
+        if self.assist != 0 {
+            parts.push(format!("A{}", self.assist));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Who serves after a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServeRotation {
+    /// The player who conceded.
+    Loser,
+    /// Two serves each, left first, as in table tennis.
+    Alternate,
+    /// The player who scored.
+    Winner,
+}
+
+impl ServeRotation {
+    pub const ALL: [ServeRotation; 3] = [
+        ServeRotation::Loser,
+        ServeRotation::Alternate,
+        ServeRotation::Winner,
+    ];
+
+    /// Name in rules files.
+    pub fn name(self) -> &'static str {
+        match self {
+            ServeRotation::Loser => "loser",
+            ServeRotation::Alternate => "alternate",
+            ServeRotation::Winner => "winner",
+        }
+    }
+
+    /// The side serving next, after `scorer` won a point and `points_played`
+    /// points have been played in the game.
+    pub fn server(self, scorer: Side, points_played: u32) -> Side {
+        match self {
+            ServeRotation::Loser => scorer.opposite(),
+            ServeRotation::Winner => scorer,
+            ServeRotation::Alternate if (points_played / 2) % 2 == 0 => Side::Left,
+            ServeRotation::Alternate => Side::Right,
+        }
+    }
+}
+
+/// Everything that tunes play: paddle size and speed, ball speed, match
+/// length, frame rate and input momentum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRules {
+    /// Paddle height in cells.
+    pub paddle_height: usize,
+    /// Cells a paddle moves per frame.
+    pub paddle_speed: usize,
+    /// The ball moves one cell every N frames (higher = slower).
+    pub ball_speed_divisor: usize,
+    /// Score that wins a classic match, or one game of a match in sets.
+    pub points_to_win: u32,
+    /// Games that win a set when playing in sets.
+    pub games_per_set: u32,
+    pub serve: ServeRotation,
+    /// Seconds a held serve waits before launching itself.
+    pub auto_serve_seconds: u32,
+    pub fps: u32,
+    /// Frames a paddle keeps moving after a key press.
+    pub momentum: u8,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+impl GameRules {
+    /// The original game.
+    pub const CLASSIC: Self = Self {
+        paddle_height: 5,
+        paddle_speed: 1,
+        ball_speed_divisor: 2,
+        points_to_win: 11,
+        games_per_set: 3,
+        serve: ServeRotation::Loser,
+        auto_serve_seconds: 3,
+        fps: 60,
+        momentum: 5,
+    };
+
+    /// Ball and paddles twice as fast.
+    pub const FAST: Self = Self {
+        paddle_speed: 2,
+        ball_speed_divisor: 1,
+        ..Self::CLASSIC
+    };
+
+    /// Big, quick paddles and short matches.
+    pub const ARCADE: Self = Self {
+        paddle_height: 7,
+        paddle_speed: 2,
+        ball_speed_divisor: 1,
+        points_to_win: 7,
+        games_per_set: 2,
+        momentum: 8,
+        ..Self::CLASSIC
+    };
+
+    /// Small paddles, long matches, alternating serves and a smoother frame rate.
+    pub const TOURNAMENT: Self = Self {
+        paddle_height: 4,
+        points_to_win: 21,
+        serve: ServeRotation::Alternate,
+        fps: 90,
+        ball_speed_divisor: 3,
+        ..Self::CLASSIC
+    };
+
+    /// Named presets, in the order the settings screen cycles through them.
+    pub const PRESETS: [(&'static str, GameRules); 4] = [
+        ("classic", Self::CLASSIC),
+        ("fast", Self::FAST),
+        ("arcade", Self::ARCADE),
+        ("tournament", Self::TOURNAMENT),
+    ];
+
+    /// A preset by name.
+    pub fn preset(name: &str) -> Option<GameRules> {
+        Self::PRESETS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, rules)| *rules)
+    }
+
+    /// Name of the preset these rules match exactly, if any.
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, rules)| rules == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// Time budget for one frame.
+    pub fn frame_duration(&self) -> Duration {
+        Duration::from_secs(1) / self.fps.max(1)
+    }
+
+    /// Set one rule from its text form. Ok(false) means `key` isn't a rule,
+    /// so callers embedding rules in a larger file can handle it themselves.
+    pub fn apply(&mut self, key: &str, value: &str) -> Result<bool, String> {
+        match key {
+            "paddle_height" => self.paddle_height = parse_in_range(value, PADDLE_HEIGHT_RANGE)?,
+            "paddle_speed" => self.paddle_speed = parse_in_range(value, PADDLE_SPEED_RANGE)?,
+            "ball_speed" => self.ball_speed_divisor = parse_in_range(value, BALL_SPEED_RANGE)?,
+            "points_to_win" => self.points_to_win = parse_in_range(value, POINTS_RANGE)?,
+            "games_per_set" => self.games_per_set = parse_in_range(value, GAMES_PER_SET_RANGE)?,
+            "auto_serve" => self.auto_serve_seconds = parse_in_range(value, AUTO_SERVE_RANGE)?,
+            "serve" => {
+                self.serve = ServeRotation::ALL
+                    .into_iter()
+                    .find(|r| r.name().eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        format!("serve must be loser, alternate or winner, got `{value}`")
+                    })?
+            }
+            "momentum" => self.momentum = parse_in_range(value, MOMENTUM_RANGE)?,
+            "fps" => {
+                self.fps = value
+                    .parse()
+                    .ok()
+                    .filter(|fps| FPS_CHOICES.contains(fps))
+                    .ok_or_else(|| format!("fps must be one of {FPS_CHOICES:?}, got `{value}`"))?
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The rule lines, without a header; also embedded in the settings file.
+    pub fn to_entries(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "paddle_height = {}", self.paddle_height);
+        let _ = writeln!(text, "paddle_speed = {}", self.paddle_speed);
+        let _ = writeln!(text, "ball_speed = {}", self.ball_speed_divisor);
+        let _ = writeln!(text, "points_to_win = {}", self.points_to_win);
+        let _ = writeln!(text, "games_per_set = {}", self.games_per_set);
+        let _ = writeln!(text, "serve = {}", self.serve.name());
+        let _ = writeln!(text, "auto_serve = {}", self.auto_serve_seconds);
+        let _ = writeln!(text, "fps = {}", self.fps);
+        let _ = writeln!(text, "momentum = {}", self.momentum);
+        text
+    }
+
+    /// A complete rules file, naming the matching preset if there is one.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Terminal Pong rules\n");
+        if let Some(name) = self.preset_name() {
+            let _ = writeln!(text, "preset = {name}");
+        }
+        text.push_str(&self.to_entries());
+        text
+    }
+
+    /// Parse a rules file. `preset = <name>` picks the starting values (classic
+    /// if absent) and the other lines override them. All problems are
+    /// collected and returned together.
+    pub fn parse(text: &str) -> Result<GameRules, Vec<ConfigError>> {
+        let (entries, mut errors) = config::parse_entries(text);
+
+        // The preset must be applied before any override, wherever it appears
+        let mut rules = Self::CLASSIC;
+        for entry in entries.iter().filter(|e| e.key == "preset") {
+            match Self::preset(&entry.value) {
+                Some(preset) => rules = preset,
+                None => errors.push(ConfigError::new(
+                    entry.line,
+                    format!("unknown preset `{}`", entry.value),
+                )),
+            }
+        }
+
+        for entry in entries.iter().filter(|e| e.key != "preset") {
+            match rules.apply(&entry.key, &entry.value) {
+                Ok(true) => {}
+                Ok(false) => errors.push(ConfigError::new(
+                    entry.line,
+                    format!("unknown rule `{}`", entry.key),
+                )),
+                Err(message) => errors.push(ConfigError::new(entry.line, message)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(rules)
+        } else {
+            errors.sort_by_key(|e| e.line);
+            Err(errors)
+        }
+    }
+
+    /// A preset by name, or else a rules file at that path.
+    pub fn load(name_or_path: &str) -> Result<GameRules, Vec<ConfigError>> {
+        if let Some(rules) = Self::preset(name_or_path) {
+            return Ok(rules);
+        }
+        let path = Path::new(name_or_path);
+        let text = fs::read_to_string(path).map_err(|e| {
+            let names: Vec<&str> = Self::PRESETS.iter().map(|(n, _)| *n).collect();
+            vec![ConfigError::new(
+                0,
+                format!("{}: {e} (presets: {})", path.display(), names.join(", ")),
+            )]
+        })?;
+        Self::parse(&text)
+    }
+}
+
+fn parse_in_range<T>(value: &str, (min, max): (T, T)) -> Result<T, String>
+where
+    T: std::str::FromStr + PartialOrd + std::fmt::Display + Copy,
+{
+    value
+        .parse::<T>()
+        .ok()
+        .filter(|v| *v >= min && *v <= max)
+        .ok_or_else(|| format!("expected a number from {min} to {max}, got `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip_through_text() {
+        for (name, rules) in GameRules::PRESETS {
+            let text = rules.to_text();
+            assert!(text.contains(&format!("preset = {name}\n")));
+            assert_eq!(GameRules::parse(&text), Ok(rules));
+        }
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_the_preset() {
+        let text = "ball_speed = 4\n# house rule\npreset = arcade\n";
+        let rules = GameRules::parse(text).unwrap();
+        assert_eq!(
+            rules,
+            GameRules {
+                ball_speed_divisor: 4,
+                ..GameRules::ARCADE
+            }
+        );
+        assert_eq!(rules.preset_name(), None);
+        assert!(!rules.to_text().contains("preset"));
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let text = "preset = blitz\npaddle_height = 40\nspin = 3\nfps = 60\n";
+        let errors = GameRules::parse(text).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "line 1: unknown preset `blitz`",
+                "line 2: expected a number from 2 to 10, got `40`",
+                "line 3: unknown rule `spin`",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_accepts_preset_names() {
+        assert_eq!(GameRules::load("Tournament"), Ok(GameRules::TOURNAMENT));
+        assert!(GameRules::load("/nonexistent/house.rules").is_err());
+    }
+
+    #[test]
+    fn frame_duration_follows_fps() {
+        let rules = GameRules {
+            fps: 50,
+            ..GameRules::CLASSIC
+        };
+        assert_eq!(rules.frame_duration(), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn serve_rotation_picks_the_next_server() {
+        assert_eq!(ServeRotation::Loser.server(Side::Left, 1), Side::Right);
+        assert_eq!(ServeRotation::Winner.server(Side::Left, 1), Side::Left);
+        let alternate: Vec<Side> = (1..=5)
+            .map(|played| ServeRotation::Alternate.server(Side::Left, played))
+            .collect();
+        assert_eq!(
+            alternate,
+            vec![Side::Left, Side::Right, Side::Right, Side::Left, Side::Left]
+        );
+        let rules = GameRules::parse("serve = Winner\nauto_serve = 5\n").unwrap();
+        assert_eq!(rules.serve, ServeRotation::Winner);
+        assert_eq!(rules.auto_serve_seconds, 5);
+    }
+
+    #[test]
+    fn handicap_adjusts_paddles_within_the_rule_ranges() {
+        let handicap = Handicap {
+            paddle_height: 3,
+            paddle_speed: -2,
+            start_score: 3,
+            assist: 0,
+        };
+        assert_eq!(handicap.paddle(&GameRules::CLASSIC), (8, 1));
+        assert_eq!(handicap.paddle(&GameRules::ARCADE), (10, 1));
+        assert_eq!(handicap.summary(), "H+3 S-2 P3");
+        assert_eq!(Handicap::default().summary(), "-");
+    }
+}
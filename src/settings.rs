@@ -0,0 +1,338 @@
+//! Player settings edited on the settings screen and saved between runs as
+//! `settings.conf` in the config directory, in the same `key = value` format
+//! as themes: the game rules plus theme and key bindings. Values that don't
+//! parse fall back to their defaults.
+
+use crate::config::{self, ConfigError};
+use crate::rules::GameRules;
+use crossterm::event::KeyCode;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name inside the config directory.
+pub const SETTINGS_FILE: &str = "settings.conf";
+
+/// A paddle movement or serve that can be bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    LeftUp,
+    LeftDown,
+    RightUp,
+    RightDown,
+    LeftServe,
+    RightServe,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::LeftUp,
+        Action::LeftDown,
+        Action::RightUp,
+        Action::RightDown,
+        Action::LeftServe,
+        Action::RightServe,
+    ];
+
+    /// Key in the settings file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Action::LeftUp => "key.left_up",
+            Action::LeftDown => "key.left_down",
+            Action::RightUp => "key.right_up",
+            Action::RightDown => "key.right_down",
+            Action::LeftServe => "key.left_serve",
+            Action::RightServe => "key.right_serve",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::LeftUp => "Left up",
+            Action::LeftDown => "Left down",
+            Action::RightUp => "Right up",
+            Action::RightDown => "Right down",
+            Action::LeftServe => "Left serve",
+            Action::RightServe => "Right serve",
+        }
+    }
+}
+
+/// Keys moving the paddles and serving. Letters are stored lowercase and
+/// match either case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub left_up: KeyCode,
+    pub left_down: KeyCode,
+    pub right_up: KeyCode,
+    pub right_down: KeyCode,
+    pub left_serve: KeyCode,
+    pub right_serve: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            left_up: KeyCode::Char('w'),
+            left_down: KeyCode::Char('s'),
+            right_up: KeyCode::Up,
+            right_down: KeyCode::Down,
+            left_serve: KeyCode::Char('d'),
+            right_serve: KeyCode::Left,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: Action) -> KeyCode {
+        match action {
+            Action::LeftUp => self.left_up,
+            Action::LeftDown => self.left_down,
+            Action::RightUp => self.right_up,
+            Action::RightDown => self.right_down,
+            Action::LeftServe => self.left_serve,
+            Action::RightServe => self.right_serve,
+        }
+    }
+
+    fn slot(&mut self, action: Action) -> &mut KeyCode {
+        match action {
+            Action::LeftUp => &mut self.left_up,
+            Action::LeftDown => &mut self.left_down,
+            Action::RightUp => &mut self.right_up,
+            Action::RightDown => &mut self.right_down,
+            Action::LeftServe => &mut self.left_serve,
+            Action::RightServe => &mut self.right_serve,
+        }
+    }
+
+    /// The action bound to `code`, if any.
+    pub fn action_for(&self, code: KeyCode) -> Option<Action> {
+        let code = normalize_key(code);
+        Action::ALL.into_iter().find(|a| self.get(*a) == code)
+    }
+
+    /// Bind `code` to `action`. An action already using the key takes over
+    /// the old key, so no key ends up doing two things. Returns false for
+    /// keys that can't be bound (see `parse_key`).
+    pub fn assign(&mut self, action: Action, code: KeyCode) -> bool {
+        let code = normalize_key(code);
+        if !is_bindable(code) {
+            return false;
+        }
+        let previous = self.get(action);
+        if let Some(other) = self.action_for(code) {
+            *self.slot(other) = previous;
+        }
+        *self.slot(action) = code;
+        true
+    }
+}
+
+fn normalize_key(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        other => other,
+    }
+}
+
+/// Arrows and printable characters, except keys the game already uses
+/// (Space pauses, Q leaves the match).
+fn is_bindable(code: KeyCode) -> bool {
+    match code {
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => true,
+        KeyCode::Char(c) => c.is_ascii_graphic() && c != 'q' && c != 'Q',
+        _ => false,
+    }
+}
+
+/// Parse a key name from the settings file: `up`, `down`, `left`, `right`,
+/// or a single character.
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let code = match name.to_ascii_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    let code = normalize_key(code);
+    is_bindable(code).then_some(code)
+}
+
+/// Name of a key as written to the settings file.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Short label for a key on screen: arrows as glyphs, letters uppercase.
+pub fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Everything on the settings screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub rules: GameRules,
+    pub theme: String,
+    pub keys: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rules: GameRules::default(),
+            theme: "classic".to_string(),
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Path of the settings file, if there is a config directory.
+    pub fn path() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join(SETTINGS_FILE))
+    }
+
+    /// Load settings from `path`. A missing file gives the defaults; problems
+    /// are returned alongside the settings so they can be shown as warnings.
+    pub fn load_from(path: &Path) -> (Settings, Vec<ConfigError>) {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (Settings::default(), Vec::new()),
+            Err(err) => (
+                Settings::default(),
+                vec![ConfigError::new(0, format!("{}: {err}", path.display()))],
+            ),
+        }
+    }
+
+    /// Parse a settings file. Each bad or unknown line is reported and the
+    /// setting keeps its default, so one typo never loses the rest.
+    pub fn parse(text: &str) -> (Settings, Vec<ConfigError>) {
+        let (entries, mut errors) = config::parse_entries(text);
+        let mut settings = Settings::default();
+        for entry in entries {
+            if let Err(message) = settings.apply(&entry.key, &entry.value) {
+                errors.push(ConfigError::new(entry.line, message));
+            }
+        }
+        errors.sort_by_key(|e| e.line);
+        (settings, errors)
+    }
+
+    fn apply(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if self.rules.apply(key, value)? {
+            return Ok(());
+        }
+        match key {
+            "theme" if !value.is_empty() => self.theme = value.to_string(),
+            "theme" => return Err("theme needs a name".to_string()),
+            _ => {
+                let action = Action::ALL
+                    .into_iter()
+                    .find(|a| a.key() == key)
+                    .ok_or_else(|| format!("unknown setting `{key}`"))?;
+                let code = parse_key(value).ok_or_else(|| format!("unusable key `{value}`"))?;
+                self.keys.assign(action, code);
+            }
+        }
+        Ok(())
+    }
+
+    /// The settings file contents.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Terminal Pong settings, written by the settings screen\n");
+        text.push_str(&self.rules.to_entries());
+        let _ = writeln!(text, "theme = {}", self.theme);
+        for action in Action::ALL {
+            let _ = writeln!(
+                text,
+                "{} = {}",
+                action.key(),
+                key_name(self.keys.get(action))
+            );
+        }
+        text
+    }
+
+    /// Write the settings to `path`, creating its directory if needed.
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_round_trips() {
+        let mut settings = Settings {
+            rules: GameRules {
+                paddle_speed: 2,
+                fps: 120,
+                ..GameRules::ARCADE
+            },
+            theme: "retro".to_string(),
+            ..Settings::default()
+        };
+        settings.keys.assign(Action::RightUp, KeyCode::Char('I'));
+        let (parsed, errors) = Settings::parse(&settings.to_text());
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(parsed, settings);
+        assert_eq!(parsed.keys.right_up, KeyCode::Char('i'));
+    }
+
+    #[test]
+    fn bad_values_fall_back_to_defaults() {
+        let text = "paddle_speed = 9\nfps = 61\nvolume = 3\nball_speed = 4\nkey.left_up = space\n";
+        let (settings, errors) = Settings::parse(text);
+        let defaults = GameRules::default();
+        assert_eq!(settings.rules.paddle_speed, defaults.paddle_speed);
+        assert_eq!(settings.rules.fps, defaults.fps);
+        assert_eq!(settings.rules.ball_speed_divisor, 4);
+        assert_eq!(settings.keys, KeyBindings::default());
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![1, 2, 3, 5]);
+        assert_eq!(errors[2].message, "unknown setting `volume`");
+    }
+
+    #[test]
+    fn binding_a_used_key_swaps_it() {
+        let mut keys = KeyBindings::default();
+        assert!(keys.assign(Action::LeftUp, KeyCode::Up));
+        assert_eq!(keys.left_up, KeyCode::Up);
+        assert_eq!(keys.right_up, KeyCode::Char('w'));
+        assert_eq!(keys.action_for(KeyCode::Char('W')), Some(Action::RightUp));
+
+        // Space and Q are taken by pause and quit
+        assert!(!keys.assign(Action::LeftDown, KeyCode::Char(' ')));
+        assert!(!keys.assign(Action::LeftDown, KeyCode::Char('Q')));
+        assert_eq!(keys.left_down, KeyCode::Char('s'));
+    }
+}
@@ -0,0 +1,136 @@
+//! Match statistics gathered from ball events while playing.
+
+use crate::model::{BallEvent, PaddleHitLocation, Side};
+use crate::rules::Handicap;
+use std::time::Duration;
+
+/// Paddle hits by one player, and the handicap they played with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerStats {
+    pub handicap: Handicap,
+    pub hits: usize,
+    /// Hits per zone, indexed by `PaddleHitLocation::index`.
+    pub zones: [usize; 5],
+}
+
+impl PlayerStats {
+    pub fn zone(&self, location: PaddleHitLocation) -> usize {
+        self.zones[location.index()]
+    }
+}
+
+/// Running totals for the current match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchStats {
+    /// Time spent playing, not counting pauses.
+    pub play_time: Duration,
+    pub longest_rally: usize,
+    /// Paddle hits in rallies that ended with a point.
+    pub finished_rally_hits: usize,
+    pub points_played: usize,
+    /// Hits by the player on each side.
+    pub left: PlayerStats,
+    pub right: PlayerStats,
+}
+
+impl MatchStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Account for the outcome of one physics tick; `rally` is the paddle hits
+    /// in the rally the event belongs to, as counted by the board.
+    pub fn record(&mut self, event: BallEvent, rally: usize) {
+        match event {
+            BallEvent::PaddleBounce => {
+                self.longest_rally = self.longest_rally.max(rally);
+            }
+            BallEvent::LeftGoal | BallEvent::RightGoal => {
+                self.finished_rally_hits += rally;
+                self.points_played += 1;
+            }
+            BallEvent::WallBounce | BallEvent::BrickHit | BallEvent::None => {}
+        }
+    }
+
+    /// Account for a paddle hit by `side` at `location`.
+    pub fn record_hit(&mut self, side: Side, location: PaddleHitLocation) {
+        let player = match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        };
+        player.hits += 1;
+        player.zones[location.index()] += 1;
+    }
+
+    pub fn player(&self, side: Side) -> &PlayerStats {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    /// Players changed ends: per-side totals follow their player.
+    pub fn swap_sides(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+
+    /// Average paddle hits per finished point.
+    pub fn average_rally(&self) -> f64 {
+        if self.points_played == 0 {
+            0.0
+        } else {
+            self.finished_rally_hits as f64 / self.points_played as f64
+        }
+    }
+}
+
+/// Format a duration as `mm:ss` (minutes keep counting past an hour).
+pub fn format_clock(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rallies_are_tracked_per_point() {
+        let mut stats = MatchStats::new();
+        for (event, rally) in [
+            (BallEvent::PaddleBounce, 1),
+            (BallEvent::WallBounce, 1),
+            (BallEvent::PaddleBounce, 2),
+            (BallEvent::PaddleBounce, 3),
+            (BallEvent::LeftGoal, 3),
+            (BallEvent::PaddleBounce, 1),
+            (BallEvent::RightGoal, 1),
+            (BallEvent::PaddleBounce, 1),
+        ] {
+            stats.record(event, rally);
+        }
+        assert_eq!(stats.longest_rally, 3);
+        assert_eq!(stats.points_played, 2);
+        // The rally in progress doesn't count towards the average yet
+        assert_eq!(stats.average_rally(), 2.0);
+    }
+
+    #[test]
+    fn hits_are_counted_per_player_and_zone() {
+        let mut stats = MatchStats::new();
+        stats.record_hit(Side::Left, PaddleHitLocation::Center);
+        stats.record_hit(Side::Left, PaddleHitLocation::TopEdge);
+        stats.record_hit(Side::Right, PaddleHitLocation::Center);
+        assert_eq!(stats.player(Side::Left).hits, 2);
+        assert_eq!(stats.left.zones, [1, 0, 1, 0, 0]);
+        assert_eq!(stats.player(Side::Right).zone(PaddleHitLocation::Center), 1);
+    }
+
+    #[test]
+    fn clock_shows_minutes_and_seconds() {
+        assert_eq!(format_clock(Duration::from_secs(0)), "00:00");
+        assert_eq!(format_clock(Duration::from_millis(135_900)), "02:15");
+        assert_eq!(format_clock(Duration::from_secs(3725)), "62:05");
+    }
+}
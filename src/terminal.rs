@@ -1,21 +1,322 @@
 //! Terminal utilities: capability checks and render styles.
 
-use std::io::{self, Write};
+use crate::framebuffer::Element;
+use crossterm::style::Color;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+/// Queries sent when probing: synchronized output mode (DECRQM 2026), kitty
+/// keyboard flags, then primary device attributes. Every terminal answers
+/// DA1, so its reply marks the end of the probe.
+const PROBE_QUERY: &str = "\x1b[?2026$p\x1b[?u\x1b[c";
+
+/// How long to wait for probe replies before assuming no support.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(250);
 
 /// Check if the terminal supports Unicode (UTF-8).
-/// Simple heuristic: check LANG/LC_ALL environment variables.
+/// Uses the locale that governs character encoding: the first non-empty of
+/// `LC_ALL`, `LC_CTYPE` and `LANG`.
 pub fn supports_unicode() -> bool {
-    if let Ok(lang) = std::env::var("LANG") {
-        if lang.contains("UTF-8") || lang.contains("utf8") {
-            return true;
+    locale_is_utf8(|name| std::env::var(name).ok())
+}
+
+/// Whether the braille ball was turned off with `PONG_SMOOTH_BALL=0`, for
+/// fonts that render braille poorly.
+pub fn smooth_ball_disabled() -> bool {
+    std::env::var("PONG_SMOOTH_BALL").is_ok_and(|v| v == "0")
+}
+
+fn locale_is_utf8(var: impl Fn(&str) -> Option<String>) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())
+        .map(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colors (`TERM=dumb` or `NO_COLOR` set).
+    Monochrome,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Convert a color to the closest one this depth can show; None when
+    /// colors are unavailable.
+    pub fn adapt(self, color: Color) -> Option<Color> {
+        match (self, color) {
+            (Self::Monochrome, _) => None,
+            (Self::TrueColor, c) => Some(c),
+            (Self::Ansi256, Color::Rgb { r, g, b }) => {
+                Some(Color::AnsiValue(rgb_to_ansi256(r, g, b)))
+            }
+            (Self::Ansi16, Color::Rgb { r, g, b }) => Some(nearest_ansi16((r, g, b))),
+            (Self::Ansi16, Color::AnsiValue(n)) if n >= 16 => {
+                Some(nearest_ansi16(ansi256_to_rgb(n)))
+            }
+            (Self::Ansi16, Color::AnsiValue(n)) => Some(ANSI16[n as usize].0),
+            (_, c) => Some(c),
         }
     }
-    if let Ok(lc_all) = std::env::var("LC_ALL") {
-        if lc_all.contains("UTF-8") || lc_all.contains("utf8") {
-            return true;
+}
+
+/// The 16 standard colors with xterm's default RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the 6×6×6 color cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+/// RGB value a terminal with xterm defaults shows for `color`; None for
+/// `Reset` (the terminal's own default).
+pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(n) => Some(ansi256_to_rgb(n)),
+        named => ANSI16
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn ansi256_to_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16[n as usize].1,
+        16..=231 => {
+            let i = n - 16;
+            let level = |v: u8| CUBE_LEVELS[v as usize];
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + (n - 232) * 10;
+            (v, v, v)
         }
     }
-    false
+}
+
+/// Closest 256-color index, choosing between the color cube and the grey ramp.
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    let avg = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    let grey = 232 + (avg.saturating_sub(3) / 10).min(23);
+    let rgb = (r, g, b);
+    if distance(rgb, ansi256_to_rgb(grey)) < distance(rgb, ansi256_to_rgb(cube)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+/// What the terminal can do, detected once per session so the renderer and
+/// input handling don't re-read the environment every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerminalCapabilities {
+    /// Unicode glyphs can be shown (UTF-8 locale, `PONG_FORCE_ASCII` unset).
+    pub unicode: bool,
+    pub colors: ColorDepth,
+    /// Synchronized output (mode 2026) is supported, so frames can be
+    /// wrapped in begin/end markers.
+    pub synchronized_output: bool,
+    /// The kitty keyboard protocol is supported, giving key release events.
+    pub kitty_keyboard: bool,
+}
+
+impl TerminalCapabilities {
+    /// Capabilities from environment variables alone; nothing is queried, so
+    /// synchronized output and the kitty protocol are assumed unsupported.
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Environment detection plus a query of the terminal itself.
+    /// Needs raw mode, otherwise the replies are echoed and line-buffered.
+    pub fn detect() -> Self {
+        let mut caps = Self::from_env();
+        // Only a real terminal will answer; anything else would hang the probe
+        if cfg!(unix) && io::stdin().is_terminal() && io::stdout().is_terminal() {
+            let reply = probe_terminal();
+            caps.synchronized_output = reply.synchronized_output;
+            caps.kitty_keyboard = reply.kitty_keyboard;
+        }
+        caps
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let set = |name: &str| var(name).is_some_and(|v| !v.is_empty());
+        let term = var("TERM").unwrap_or_default();
+        let colorterm = var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+
+        let colors = if set("NO_COLOR") || term == "dumb" {
+            ColorDepth::Monochrome
+        } else if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        Self {
+            unicode: var("PONG_FORCE_ASCII").is_none() && locale_is_utf8(&var),
+            colors,
+            synchronized_output: false,
+            kitty_keyboard: false,
+        }
+    }
+}
+
+/// Features confirmed by the terminal's replies to `PROBE_QUERY`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ProbeReply {
+    synchronized_output: bool,
+    kitty_keyboard: bool,
+    /// The DA1 reply arrived, so no further replies are coming.
+    complete: bool,
+}
+
+/// Send the probe queries and read the replies from the stdin descriptor,
+/// waiting at most `PROBE_TIMEOUT` in all so a silent terminal can't block
+/// startup. Bytes are read one at a time, unbuffered, and reading stops at
+/// the DA1 reply, so keys typed afterwards are left for the event reader.
+#[cfg(unix)]
+fn probe_terminal() -> ProbeReply {
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let mut out = io::stdout();
+    if out
+        .write_all(PROBE_QUERY.as_bytes())
+        .and_then(|_| out.flush())
+        .is_err()
+    {
+        return ProbeReply::default();
+    }
+
+    let fd = io::stdin().as_raw_fd();
+    let deadline = Instant::now() + PROBE_TIMEOUT;
+    let mut bytes = Vec::new();
+    let mut reply = ProbeReply::default();
+    while !reply.complete {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() || !wait_readable(fd, left) {
+            break;
+        }
+        let Some(byte) = read_byte(fd) else {
+            break;
+        };
+        bytes.push(byte);
+        reply = parse_probe_reply(&bytes);
+    }
+    reply
+}
+
+#[cfg(not(unix))]
+fn probe_terminal() -> ProbeReply {
+    ProbeReply::default()
+}
+
+/// Wait up to `timeout` for `fd` to have input. Errors count as none.
+#[cfg(unix)]
+fn wait_readable(fd: libc::c_int, timeout: Duration) -> bool {
+    let mut pfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let ms = timeout.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+    // SAFETY: `pfd` is a valid pollfd for the duration of the call
+    let ready = unsafe { libc::poll(&mut pfd, 1, ms) };
+    ready > 0 && pfd.revents & libc::POLLIN != 0
+}
+
+/// Read a single byte; None at end of input or on error.
+#[cfg(unix)]
+fn read_byte(fd: libc::c_int) -> Option<u8> {
+    let mut byte = 0u8;
+    // SAFETY: the buffer is one valid, writable byte
+    let n = unsafe { libc::read(fd, (&mut byte as *mut u8).cast(), 1) };
+    (n == 1).then_some(byte)
+}
+
+/// Scan `ESC [ ?` replies: DECRPM (`2026;Ps$y`), kitty flags (`flags u`) and
+/// DA1 (`...c`). Other bytes, such as keys typed meanwhile, are skipped.
+fn parse_probe_reply(bytes: &[u8]) -> ProbeReply {
+    let mut reply = ProbeReply::default();
+    let mut rest = bytes;
+    while let Some(start) = rest.windows(3).position(|w| w == b"\x1b[?") {
+        rest = &rest[start + 3..];
+        let params_len = rest
+            .iter()
+            .take_while(|b| (0x30..=0x3F).contains(*b))
+            .count();
+        let (params, tail) = rest.split_at(params_len);
+        let inter_len = tail
+            .iter()
+            .take_while(|b| (0x20..=0x2F).contains(*b))
+            .count();
+        let (inter, tail) = tail.split_at(inter_len);
+        let Some(&final_byte) = tail.first() else {
+            break;
+        };
+        rest = &tail[1..];
+
+        match (final_byte, inter) {
+            // Mode status 1 (set), 2 (reset) or 3 (permanently set) means supported
+            (b'y', b"$") => {
+                if let Some(status) = params.strip_prefix(b"2026;") {
+                    reply.synchronized_output |= matches!(status, b"1" | b"2" | b"3");
+                }
+            }
+            (b'u', b"") => reply.kitty_keyboard = true,
+            (b'c', b"") => reply.complete = true,
+            _ => {}
+        }
+    }
+    reply
 }
 
 /// Print terminal setup instructions and wait for user confirmation.
@@ -54,6 +355,52 @@ pub fn print_setup_instructions(required_width: usize, required_height: usize) -
     Ok(())
 }
 
+/// Foreground colors per element, plus an optional background.
+/// None leaves the terminal's default color.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Palette {
+    pub border: Option<Color>,
+    pub court: Option<Color>,
+    pub paddle: Option<Color>,
+    pub ball: Option<Color>,
+    pub brick: Option<Color>,
+    pub text: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Palette {
+    /// True when no color is set at all (plain monochrome output).
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Foreground color for cells drawn as `element`.
+    pub fn color(&self, element: Element) -> Option<Color> {
+        match element {
+            Element::Text => self.text,
+            Element::Border => self.border,
+            Element::Court => self.court,
+            Element::Paddle => self.paddle,
+            Element::Ball => self.ball,
+            Element::Brick => self.brick,
+        }
+    }
+
+    /// This palette with every color converted for the given depth.
+    pub fn adapted(self, depth: ColorDepth) -> Self {
+        let adapt = |c: Option<Color>| c.and_then(|c| depth.adapt(c));
+        Self {
+            border: adapt(self.border),
+            court: adapt(self.court),
+            paddle: adapt(self.paddle),
+            ball: adapt(self.ball),
+            brick: adapt(self.brick),
+            text: adapt(self.text),
+            background: adapt(self.background),
+        }
+    }
+}
+
 /// Terminal render style based on capabilities.
 #[derive(Debug, Clone, Copy)]
 pub struct RenderStyle {
@@ -63,8 +410,30 @@ pub struct RenderStyle {
     pub border_corner_tr: char,
     pub border_corner_bl: char,
     pub border_corner_br: char,
+    /// Joints where a separator meets the left/right border.
+    pub border_tee_left: char,
+    pub border_tee_right: char,
     pub paddle: char,
     pub ball: char,
+    /// Draw the ball as braille dots so it glides between cells (Unicode only).
+    pub smooth_ball: bool,
+    /// Ball trail glyphs, newest to oldest.
+    pub trail: [char; 3],
+    pub paddle_flash: char,
+    pub spark: char,
+    /// Breakout brick fill.
+    pub brick: char,
+    /// Fill glyph for big scoreboard digits; None draws plain digits.
+    pub score_block: Option<char>,
+    /// Dashed center net.
+    pub net: char,
+    /// Service line glyph; None leaves the court unmarked.
+    pub service_mark: Option<char>,
+    /// Marker in front of the selected menu item.
+    pub menu_marker: char,
+    pub progress_filled: char,
+    pub progress_empty: char,
+    pub colors: Palette,
 }
 
 impl RenderStyle {
@@ -77,8 +446,22 @@ impl RenderStyle {
             border_corner_tr: '+',
             border_corner_bl: '+',
             border_corner_br: '+',
+            border_tee_left: '+',
+            border_tee_right: '+',
             paddle: '|',
             ball: 'o',
+            smooth_ball: false,
+            trail: ['.', '.', '.'],
+            paddle_flash: '#',
+            spark: '*',
+            brick: '#',
+            score_block: None,
+            net: ':',
+            service_mark: None,
+            menu_marker: '>',
+            progress_filled: '#',
+            progress_empty: '.',
+            colors: Palette::default(),
         }
     }
 
@@ -91,21 +474,167 @@ impl RenderStyle {
             border_corner_tr: '┐',
             border_corner_bl: '└',
             border_corner_br: '┘',
+            border_tee_left: '├',
+            border_tee_right: '┤',
             paddle: '█',
             ball: '●',
+            smooth_ball: true,
+            trail: ['•', '∙', '·'],
+            paddle_flash: '▓',
+            spark: '✦',
+            brick: '▒',
+            score_block: Some('█'),
+            net: '┊',
+            service_mark: None,
+            menu_marker: '▶',
+            progress_filled: '█',
+            progress_empty: '░',
+            colors: Palette::default(),
         }
     }
 
     /// Auto-detect best style based on terminal capabilities.
     pub fn auto() -> Self {
-        // Allow forcing ASCII to avoid font/terminal artifacts with block glyphs
-        if std::env::var("PONG_FORCE_ASCII").is_ok() {
-            return Self::ascii();
-        }
-        if supports_unicode() {
-            Self::unicode()
+        Self::for_capabilities(&TerminalCapabilities::from_env())
+    }
+
+    /// Best default style for already-detected capabilities.
+    pub fn for_capabilities(caps: &TerminalCapabilities) -> Self {
+        if caps.unicode {
+            let mut style = Self::unicode();
+            style.smooth_ball = !smooth_ball_disabled();
+            style
         } else {
             Self::ascii()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_colors_each_element() {
+        let palette = Palette {
+            border: Some(Color::Blue),
+            court: Some(Color::DarkGrey),
+            paddle: Some(Color::White),
+            ball: Some(Color::Yellow),
+            brick: Some(Color::Red),
+            text: Some(Color::Green),
+            background: None,
+        };
+        assert_eq!(palette.color(Element::Border), Some(Color::Blue));
+        assert_eq!(palette.color(Element::Court), Some(Color::DarkGrey));
+        assert_eq!(palette.color(Element::Paddle), Some(Color::White));
+        assert_eq!(palette.color(Element::Ball), Some(Color::Yellow));
+        assert_eq!(palette.color(Element::Brick), Some(Color::Red));
+        assert_eq!(palette.color(Element::Text), Some(Color::Green));
+    }
+
+    fn caps(vars: &[(&str, &str)]) -> TerminalCapabilities {
+        TerminalCapabilities::from_vars(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn locale_precedence_follows_lc_all_then_lc_ctype_then_lang() {
+        assert!(caps(&[("LANG", "en_US.UTF-8")]).unicode);
+        assert!(caps(&[("LC_CTYPE", "en_US.utf8"), ("LANG", "C")]).unicode);
+        assert!(!caps(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]).unicode);
+        // Empty values don't count as set
+        assert!(caps(&[("LC_ALL", ""), ("LANG", "de_DE.UTF-8")]).unicode);
+        assert!(!caps(&[("PONG_FORCE_ASCII", "1"), ("LANG", "en_US.UTF-8")]).unicode);
+        assert!(!caps(&[]).unicode);
+    }
+
+    #[test]
+    fn color_depth_from_colorterm_and_term() {
+        assert_eq!(
+            caps(&[("COLORTERM", "truecolor")]).colors,
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            caps(&[("COLORTERM", "24bit")]).colors,
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            caps(&[("TERM", "xterm-256color")]).colors,
+            ColorDepth::Ansi256
+        );
+        assert_eq!(caps(&[("TERM", "xterm")]).colors, ColorDepth::Ansi16);
+        assert_eq!(caps(&[("TERM", "dumb")]).colors, ColorDepth::Monochrome);
+        assert_eq!(
+            caps(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]).colors,
+            ColorDepth::Monochrome
+        );
+    }
+
+    #[test]
+    fn probe_reply_detects_sync_and_kitty() {
+        let full = parse_probe_reply(b"\x1b[?2026;2$y\x1b[?0u\x1b[?62;22c");
+        assert!(full.synchronized_output && full.kitty_keyboard && full.complete);
+
+        // Mode 0 (not recognized) and no kitty reply before DA1
+        let none = parse_probe_reply(b"\x1b[?2026;0$y\x1b[?1;2c");
+        assert!(!none.synchronized_output && !none.kitty_keyboard && none.complete);
+
+        // Stray keys are skipped; an unfinished reply is incomplete
+        let partial = parse_probe_reply(b"w\x1b[?2026;1$yw\x1b[?6");
+        assert!(partial.synchronized_output && !partial.complete);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_reads_only_what_is_waiting() {
+        use std::os::fd::AsRawFd;
+        use std::os::unix::net::UnixStream;
+
+        let (mut tx, rx) = UnixStream::pair().unwrap();
+        let fd = rx.as_raw_fd();
+        assert!(!wait_readable(fd, Duration::from_millis(1)));
+
+        tx.write_all(b"ok").unwrap();
+        assert!(wait_readable(fd, Duration::from_millis(100)));
+        assert_eq!(read_byte(fd), Some(b'o'));
+        assert_eq!(read_byte(fd), Some(b'k'));
+        assert!(!wait_readable(fd, Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn colors_are_downgraded_to_the_terminal_depth() {
+        let orange = Color::Rgb {
+            r: 255,
+            g: 135,
+            b: 0,
+        };
+        assert_eq!(ColorDepth::TrueColor.adapt(orange), Some(orange));
+        assert_eq!(
+            ColorDepth::Ansi256.adapt(orange),
+            Some(Color::AnsiValue(208))
+        );
+        assert_eq!(
+            ColorDepth::Ansi256.adapt(Color::Rgb {
+                r: 128,
+                g: 128,
+                b: 128
+            }),
+            Some(Color::AnsiValue(244))
+        );
+        assert_eq!(ColorDepth::Ansi16.adapt(orange), Some(Color::DarkYellow));
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::AnsiValue(196)),
+            Some(Color::Red)
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::AnsiValue(4)),
+            Some(Color::DarkBlue)
+        );
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Green), Some(Color::Green));
+        assert_eq!(ColorDepth::Monochrome.adapt(Color::Green), None);
+    }
+}
@@ -0,0 +1,421 @@
+//! Named themes: built-in glyph/color sets plus user themes loaded from
+//! `<config dir>/themes/<name>.theme`.
+//!
+//! Theme files use the `key = value` format from `config`:
+//!
+//! ```text
+//! # Amber CRT
+//! base = rounded
+//! paddle = ▌
+//! color.paddle = #ffb000
+//! color.background = black
+//! ```
+//!
+//! `base` picks the built-in theme to start from (default `classic`); every
+//! other key overrides one glyph or color. Unknown keys are reported.
+
+use crate::config::{self, ConfigError};
+use crate::terminal::{Palette, RenderStyle};
+use crossterm::style::Color;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory for user themes, inside the config directory.
+pub const THEMES_DIR: &str = "themes";
+
+/// File extension for user themes.
+pub const THEME_EXTENSION: &str = "theme";
+
+/// Built-in theme names, in picker order.
+pub const BUILTIN_THEMES: &[&str] = &["classic", "retro", "high-contrast", "double", "rounded"];
+
+/// A named render style. Glyphs are Unicode; `resolve` falls back to ASCII
+/// glyphs (keeping the colors) when the terminal can't show them.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub style: RenderStyle,
+}
+
+impl Theme {
+    /// Look up a built-in theme by name.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        let mut style = RenderStyle::unicode();
+        match name {
+            "classic" => {}
+            "retro" => {
+                style.colors = Palette {
+                    border: Some(Color::DarkGreen),
+                    court: Some(Color::DarkGreen),
+                    paddle: Some(Color::Green),
+                    ball: Some(Color::Green),
+                    brick: Some(Color::DarkGreen),
+                    text: Some(Color::Green),
+                    background: Some(Color::Black),
+                };
+            }
+            "high-contrast" => {
+                style.colors = Palette {
+                    border: Some(Color::White),
+                    court: Some(Color::Grey),
+                    paddle: Some(Color::White),
+                    ball: Some(Color::Yellow),
+                    brick: Some(Color::Cyan),
+                    text: Some(Color::White),
+                    background: Some(Color::Black),
+                };
+            }
+            "double" => {
+                style.border_horizontal = '═';
+                style.border_vertical = '║';
+                style.border_corner_tl = '╔';
+                style.border_corner_tr = '╗';
+                style.border_corner_bl = '╚';
+                style.border_corner_br = '╝';
+                style.border_tee_left = '╠';
+                style.border_tee_right = '╣';
+            }
+            "rounded" => {
+                style.border_corner_tl = '╭';
+                style.border_corner_tr = '╮';
+                style.border_corner_bl = '╰';
+                style.border_corner_br = '╯';
+            }
+            _ => return None,
+        }
+        Some(Theme {
+            name: name.to_string(),
+            style,
+        })
+    }
+
+    /// Directory holding user theme files.
+    pub fn user_dir() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join(THEMES_DIR))
+    }
+
+    /// Built-in names followed by user themes found in the config directory.
+    pub fn available() -> Vec<String> {
+        Self::available_in(Self::user_dir().as_deref())
+    }
+
+    /// Built-in names followed by user themes found in `dir`, if any.
+    pub fn available_in(dir: Option<&Path>) -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|s| s.to_string()).collect();
+        let mut custom: Vec<String> = dir
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != THEME_EXTENSION {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .filter(|name| !BUILTIN_THEMES.contains(&name.as_str()))
+            .collect();
+        custom.sort();
+        names.extend(custom);
+        names
+    }
+
+    /// Load a theme by name from the built-ins and the user theme directory.
+    pub fn load(name: &str) -> Result<Theme, Vec<ConfigError>> {
+        Self::load_from(name, Self::user_dir().as_deref())
+    }
+
+    /// Load a theme by name: built-ins first, then `<name>.theme` in `dir`.
+    /// Names that could point outside `dir` are rejected.
+    pub fn load_from(name: &str, dir: Option<&Path>) -> Result<Theme, Vec<ConfigError>> {
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(vec![ConfigError::new(
+                0,
+                format!("invalid theme name `{name}`: it can't contain `/`, `\\` or `..`"),
+            )]);
+        }
+        let path = dir
+            .map(|dir| dir.join(format!("{name}.{THEME_EXTENSION}")))
+            .filter(|p| p.is_file())
+            .ok_or_else(|| {
+                vec![ConfigError::new(
+                    0,
+                    format!(
+                        "unknown theme `{name}` (available: {})",
+                        Self::available_in(dir).join(", ")
+                    ),
+                )]
+            })?;
+        let text = fs::read_to_string(&path)
+            .map_err(|e| vec![ConfigError::new(0, format!("{}: {e}", path.display()))])?;
+        Self::parse(name, &text)
+    }
+
+    /// Parse a theme file. All problems are collected and returned together.
+    pub fn parse(name: &str, text: &str) -> Result<Theme, Vec<ConfigError>> {
+        let (entries, mut errors) = config::parse_entries(text);
+
+        // The base must be applied before any override, wherever it appears
+        let mut theme = Self::builtin("classic").expect("classic theme exists");
+        for entry in entries.iter().filter(|e| e.key == "base") {
+            match Self::builtin(&entry.value) {
+                Some(base) => theme = base,
+                None => errors.push(ConfigError::new(
+                    entry.line,
+                    format!("unknown base theme `{}`", entry.value),
+                )),
+            }
+        }
+        theme.name = name.to_string();
+
+        for entry in entries.iter().filter(|e| e.key != "base") {
+            if let Err(message) = apply(&mut theme.style, &entry.key, &entry.value) {
+                errors.push(ConfigError::new(entry.line, message));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(theme)
+        } else {
+            errors.sort_by_key(|e| e.line);
+            Err(errors)
+        }
+    }
+
+    /// Final style for a terminal: the theme as-is with Unicode, otherwise
+    /// ASCII glyphs in the theme's colors.
+    pub fn resolve(&self, unicode: bool) -> RenderStyle {
+        if unicode {
+            self.style
+        } else {
+            RenderStyle {
+                colors: self.style.colors,
+                ..RenderStyle::ascii()
+            }
+        }
+    }
+}
+
+/// Apply one theme key to a style.
+fn apply(style: &mut RenderStyle, key: &str, value: &str) -> Result<(), String> {
+    if let Some(element) = key.strip_prefix("color.") {
+        let color = parse_color(value)?;
+        let c = &mut style.colors;
+        let slot = match element {
+            "border" => &mut c.border,
+            "court" => &mut c.court,
+            "paddle" => &mut c.paddle,
+            "ball" => &mut c.ball,
+            "brick" => &mut c.brick,
+            "text" => &mut c.text,
+            "background" => &mut c.background,
+            _ => return Err(format!("unknown key `{key}`")),
+        };
+        *slot = color;
+        return Ok(());
+    }
+
+    let slot = match key {
+        "border_horizontal" => &mut style.border_horizontal,
+        "border_vertical" => &mut style.border_vertical,
+        "corner_top_left" => &mut style.border_corner_tl,
+        "corner_top_right" => &mut style.border_corner_tr,
+        "corner_bottom_left" => &mut style.border_corner_bl,
+        "corner_bottom_right" => &mut style.border_corner_br,
+        "tee_left" => &mut style.border_tee_left,
+        "tee_right" => &mut style.border_tee_right,
+        "paddle" => &mut style.paddle,
+        "ball" => &mut style.ball,
+        "paddle_flash" => &mut style.paddle_flash,
+        "spark" => &mut style.spark,
+        "brick" => &mut style.brick,
+        "net" => &mut style.net,
+        "menu_marker" => &mut style.menu_marker,
+        "progress_filled" => &mut style.progress_filled,
+        "progress_empty" => &mut style.progress_empty,
+        "service_mark" => {
+            style.service_mark = parse_optional_glyph(value)?;
+            return Ok(());
+        }
+        "score_block" => {
+            style.score_block = parse_optional_glyph(value)?;
+            return Ok(());
+        }
+        "trail" => {
+            let glyphs: Vec<char> = value.chars().filter(|c| !c.is_whitespace()).collect();
+            if glyphs.len() != style.trail.len() {
+                return Err(format!("`trail` needs {} glyphs", style.trail.len()));
+            }
+            for (slot, glyph) in style.trail.iter_mut().zip(glyphs) {
+                *slot = parse_glyph(&glyph.to_string())?;
+            }
+            return Ok(());
+        }
+        "smooth_ball" => {
+            style.smooth_ball = match value {
+                "true" | "yes" | "on" => true,
+                "false" | "no" | "off" => false,
+                _ => {
+                    return Err(format!(
+                        "`smooth_ball` must be true or false, got `{value}`"
+                    ))
+                }
+            };
+            return Ok(());
+        }
+        _ => return Err(format!("unknown key `{key}`")),
+    };
+    *slot = parse_glyph(value)?;
+    Ok(())
+}
+
+/// A glyph must be exactly one single-column character.
+fn parse_glyph(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if crate::util::char_width(ch) == 1 => Ok(ch),
+        _ => Err(format!(
+            "glyph must be a single narrow character, got `{value}`"
+        )),
+    }
+}
+
+/// A glyph, or `none` to disable the element.
+fn parse_optional_glyph(value: &str) -> Result<Option<char>, String> {
+    if value == "none" {
+        Ok(None)
+    } else {
+        parse_glyph(value).map(Some)
+    }
+}
+
+/// Parse a color: a name (`green`, `dark_grey`), `#rrggbb`, a 0-255 palette
+/// index, or `default` for the terminal's own color.
+pub fn parse_color(value: &str) -> Result<Option<Color>, String> {
+    if value == "default" || value == "none" {
+        return Ok(None);
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() == 6 {
+            if let Ok(rgb) = u32::from_str_radix(hex, 16) {
+                return Ok(Some(Color::Rgb {
+                    r: (rgb >> 16) as u8,
+                    g: (rgb >> 8) as u8,
+                    b: rgb as u8,
+                }));
+            }
+        }
+        return Err(format!("bad hex color `{value}` (expected #rrggbb)"));
+    }
+    if let Ok(index) = value.parse::<u8>() {
+        return Ok(Some(Color::AnsiValue(index)));
+    }
+    Color::try_from(value)
+        .map(Some)
+        .map_err(|_| format!("unknown color `{value}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_names_cannot_leave_the_theme_directory() {
+        let dir = std::env::temp_dir().join(format!("pong-themes-{}", std::process::id()));
+        let themes = dir.join(THEMES_DIR);
+        fs::create_dir_all(&themes).unwrap();
+        fs::write(dir.join("outside.theme"), "paddle = X\n").unwrap();
+        fs::write(themes.join("inside.theme"), "paddle = X\n").unwrap();
+
+        let inside = Theme::load_from("inside", Some(&themes));
+        let escapes: Vec<_> = ["../outside", "..\\outside", "a/b", "..", ""]
+            .iter()
+            .map(|name| Theme::load_from(name, Some(&themes)))
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(inside.unwrap().style.paddle, 'X');
+        for result in escapes {
+            let errors = result.unwrap_err();
+            assert!(
+                errors[0].message.contains("invalid theme name"),
+                "{errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn builtins_all_load() {
+        for name in BUILTIN_THEMES {
+            let theme = Theme::builtin(name).unwrap();
+            assert_eq!(theme.name, *name);
+        }
+        assert!(Theme::builtin("nope").is_none());
+        assert_eq!(
+            Theme::builtin("double").unwrap().style.border_corner_tl,
+            '╔'
+        );
+        assert_eq!(
+            Theme::builtin("rounded").unwrap().style.border_corner_br,
+            '╯'
+        );
+    }
+
+    #[test]
+    fn parse_applies_base_then_overrides() {
+        let text = "paddle = ▌\nbase = double\ncolor.ball = #ffcc00\nservice_mark = ┈\n";
+        let theme = Theme::parse("amber", text).unwrap();
+        assert_eq!(theme.name, "amber");
+        assert_eq!(theme.style.border_corner_tl, '╔');
+        assert_eq!(theme.style.paddle, '▌');
+        assert_eq!(theme.style.service_mark, Some('┈'));
+        assert_eq!(
+            theme.style.colors.ball,
+            Some(Color::Rgb {
+                r: 0xff,
+                g: 0xcc,
+                b: 0
+            })
+        );
+    }
+
+    #[test]
+    fn parse_reports_every_problem_with_lines() {
+        let text = "padle = x\nball = 日\ncolor.ball = purple\ncolor.sky = red\nbase = nope\n";
+        let errors: Vec<String> = Theme::parse("bad", text)
+            .unwrap_err()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 1: unknown key `padle`",
+                "line 2: glyph must be a single narrow character, got `日`",
+                "line 3: unknown color `purple`",
+                "line 4: unknown key `color.sky`",
+                "line 5: unknown base theme `nope`",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_color_formats() {
+        assert_eq!(parse_color("dark_green"), Ok(Some(Color::DarkGreen)));
+        assert_eq!(parse_color("208"), Ok(Some(Color::AnsiValue(208))));
+        assert_eq!(parse_color("default"), Ok(None));
+        assert!(parse_color("#12345").is_err());
+    }
+
+    #[test]
+    fn resolve_falls_back_to_ascii_glyphs_keeping_colors() {
+        let theme = Theme::builtin("retro").unwrap();
+        let style = theme.resolve(false);
+        assert_eq!(style.border_corner_tl, '+');
+        assert_eq!(style.colors, theme.style.colors);
+        assert_eq!(theme.resolve(true).border_corner_tl, '┌');
+    }
+}
@@ -1,14 +1,182 @@
 //! Utility functions for the game.
 
-/// Calculate the display width of a character.
-/// For our game, all characters we use are single-width:
-/// - ASCII characters
-/// - Box-drawing characters (│, ─, ┌, ┐, └, ┘, etc.)
-/// - Game symbols (●, █, ↑, ↓)
-pub fn char_width(_ch: char) -> usize {
-    // All characters we use in the game have display width of 1
-    // This includes ASCII, box-drawing characters, and our game symbols
-    1
+/// Inclusive code point ranges that take no columns: combining marks,
+/// zero-width format characters, variation selectors and emoji modifiers.
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0711, 0x0711),
+    (0x0730, 0x074A),
+    (0x0900, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF), // Hangul medial vowels and final consonants combine
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0000, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Inclusive code point ranges drawn two columns wide: East Asian Wide and
+/// Fullwidth characters plus emoji with default emoji presentation.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F202),
+    (0x1F210, 0x1F23B),
+    (0x1F240, 0x1F248),
+    (0x1F250, 0x1F251),
+    (0x1F260, 0x1F265),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6DC, 0x1F6DF),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+/// Binary search a sorted table of inclusive ranges.
+fn in_table(table: &[(u32, u32)], cp: u32) -> bool {
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < cp {
+                std::cmp::Ordering::Less
+            } else if lo > cp {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Calculate the display width of a character in terminal columns.
+/// - 0: control characters, combining marks, zero-width joiners/spaces,
+///   variation selectors and emoji skin-tone modifiers
+/// - 2: East Asian Wide/Fullwidth (CJK, Hangul, fullwidth forms) and emoji
+/// - 1: everything else, including box drawing and our game symbols
+///   (ambiguous-width characters are treated as narrow)
+pub fn char_width(ch: char) -> usize {
+    let cp = ch as u32;
+    if cp < 0x20 || (0x7F..0xA0).contains(&cp) {
+        return 0;
+    }
+    if cp < 0x300 {
+        return 1; // fast path for Latin text
+    }
+    if in_table(ZERO_WIDTH, cp) {
+        0
+    } else if in_table(WIDE, cp) {
+        2
+    } else {
+        1
+    }
 }
 
 /// Calculate the display width of a string.
@@ -42,6 +210,41 @@ mod tests {
         assert_eq!(char_width('↓'), 1);
     }
 
+    #[test]
+    fn test_wide_and_zero_width_chars() {
+        // East Asian wide and fullwidth
+        assert_eq!(char_width('日'), 2);
+        assert_eq!(char_width('한'), 2);
+        assert_eq!(char_width('Ａ'), 2);
+        assert_eq!(char_width('。'), 2);
+
+        // Emoji presentation
+        assert_eq!(char_width('🏓'), 2);
+        assert_eq!(char_width('😀'), 2);
+        assert_eq!(char_width('⚡'), 2);
+
+        // Zero width: combining marks, joiners, selectors, modifiers, controls
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('\u{200D}'), 0);
+        assert_eq!(char_width('\u{FE0F}'), 0);
+        assert_eq!(char_width('\u{1F3FB}'), 0);
+        assert_eq!(char_width('\0'), 0);
+
+        // Ambiguous width stays narrow
+        assert_eq!(char_width('×'), 1);
+        assert_eq!(char_width('·'), 1);
+    }
+
+    #[test]
+    fn test_tables_are_sorted_and_disjoint() {
+        for table in [ZERO_WIDTH, WIDE] {
+            for pair in table.windows(2) {
+                assert!(pair[0].0 <= pair[0].1);
+                assert!(pair[0].1 < pair[1].0);
+            }
+        }
+    }
+
     #[test]
     fn test_str_widths() {
         assert_eq!(str_width("Hello"), 5);
@@ -50,5 +253,8 @@ mod tests {
         assert_eq!(str_width("W/S"), 3);
         assert_eq!(str_width("╔═══╗"), 5);
         assert_eq!(str_width("│●█│"), 4);
+        assert_eq!(str_width("日本語"), 6);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("👍🏽"), 2);
     }
 }
@@ -0,0 +1,388 @@
+//! Small widget toolkit for menus and dialogs, drawn into a FrameBuffer.
+//! Every widget sizes itself from its content and takes its glyphs from
+//! RenderStyle, so menus fall back to ASCII along with the board.
+
+use crate::framebuffer::{Element, FrameBuffer};
+use crate::terminal::RenderStyle;
+use crate::util::{char_width, str_width};
+
+/// Something that can be measured and drawn at a position.
+pub trait Widget {
+    /// Width and height in cells needed to draw this widget.
+    fn size(&self) -> (usize, usize);
+
+    /// Draw with the widget's top-left corner at (x, y).
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, style: &RenderStyle);
+
+    /// Draw into a buffer sized exactly to the widget.
+    fn render(&self, style: &RenderStyle) -> FrameBuffer {
+        let (w, h) = self.size();
+        let mut fb = FrameBuffer::new(w, h, ' ');
+        self.draw(&mut fb, 0, 0, style);
+        fb
+    }
+}
+
+/// Write `text` at (x, y), advancing by display width.
+fn put_text(fb: &mut FrameBuffer, mut x: usize, y: usize, text: &str) {
+    for ch in text.chars() {
+        fb.set(x, y, ch);
+        x += char_width(ch);
+    }
+}
+
+/// A single line of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub text: String,
+}
+
+impl Label {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+impl Widget for Label {
+    fn size(&self) -> (usize, usize) {
+        (str_width(&self.text), 1)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, _style: &RenderStyle) {
+        put_text(fb, x, y, &self.text);
+    }
+}
+
+/// Empty rows between other widgets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacer {
+    pub height: usize,
+}
+
+impl Widget for Spacer {
+    fn size(&self) -> (usize, usize) {
+        (0, self.height)
+    }
+
+    fn draw(&self, _fb: &mut FrameBuffer, _x: usize, _y: usize, _style: &RenderStyle) {}
+}
+
+/// Vertical list of items with one highlighted selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Menu {
+    pub items: Vec<String>,
+    pub selected: usize,
+}
+
+impl Menu {
+    pub fn new(items: &[&str], selected: usize) -> Self {
+        Self {
+            items: items.iter().map(|s| s.to_string()).collect(),
+            selected,
+        }
+    }
+}
+
+impl Widget for Menu {
+    fn size(&self) -> (usize, usize) {
+        // Two columns for the selection marker and a space
+        let w = self.items.iter().map(|i| str_width(i)).max().unwrap_or(0);
+        (w + 2, self.items.len())
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, style: &RenderStyle) {
+        for (i, item) in self.items.iter().enumerate() {
+            if i == self.selected {
+                fb.set(x, y + i, style.menu_marker);
+            }
+            put_text(fb, x + 2, y + i, item);
+        }
+    }
+}
+
+/// Footer line of key hints, e.g. "Space Resume  Q Quit".
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyHints {
+    pub hints: Vec<(String, String)>,
+}
+
+impl KeyHints {
+    pub fn new(hints: &[(&str, &str)]) -> Self {
+        Self {
+            hints: hints
+                .iter()
+                .map(|(k, a)| (k.to_string(), a.to_string()))
+                .collect(),
+        }
+    }
+
+    fn text(&self) -> String {
+        self.hints
+            .iter()
+            .map(|(key, action)| format!("{key} {action}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+impl Widget for KeyHints {
+    fn size(&self) -> (usize, usize) {
+        (str_width(&self.text()), 1)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, _style: &RenderStyle) {
+        put_text(fb, x, y, &self.text());
+    }
+}
+
+/// Horizontal bar filled in proportion to `value / max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressBar {
+    pub value: u32,
+    pub max: u32,
+    pub width: usize,
+}
+
+impl ProgressBar {
+    pub fn new(value: u32, max: u32, width: usize) -> Self {
+        Self { value, max, width }
+    }
+
+    /// Number of filled cells, rounded down and clamped to the bar.
+    fn filled(&self) -> usize {
+        if self.max == 0 {
+            return 0;
+        }
+        let value = self.value.min(self.max) as usize;
+        value * self.width / self.max as usize
+    }
+}
+
+impl Widget for ProgressBar {
+    fn size(&self) -> (usize, usize) {
+        (self.width, 1)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, style: &RenderStyle) {
+        let filled = self.filled();
+        for i in 0..self.width {
+            let ch = if i < filled {
+                style.progress_filled
+            } else {
+                style.progress_empty
+            };
+            fb.set(x + i, y, ch);
+        }
+    }
+}
+
+/// Bordered panel with a title row, stacked content and an optional footer.
+pub struct Panel {
+    pub title: String,
+    pub children: Vec<Box<dyn Widget>>,
+    pub footer: Option<KeyHints>,
+}
+
+impl Panel {
+    /// Columns between the border and the content on each side.
+    const PADDING: usize = 2;
+
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            children: Vec::new(),
+            footer: None,
+        }
+    }
+
+    /// Append a child widget below the previous one.
+    pub fn with(mut self, child: impl Widget + 'static) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Append a line of text.
+    pub fn line(self, text: impl Into<String>) -> Self {
+        self.with(Label::new(text))
+    }
+
+    /// Append blank rows.
+    pub fn gap(self, height: usize) -> Self {
+        self.with(Spacer { height })
+    }
+
+    pub fn footer(mut self, hints: KeyHints) -> Self {
+        self.footer = Some(hints);
+        self
+    }
+
+    fn inner_width(&self) -> usize {
+        let children = self.children.iter().map(|c| c.size().0);
+        let footer = self.footer.iter().map(|f| f.size().0);
+        children
+            .chain(footer)
+            .chain(std::iter::once(str_width(&self.title)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Draw a separator row joined to the side borders.
+    fn draw_separator(
+        &self,
+        fb: &mut FrameBuffer,
+        x: usize,
+        y: usize,
+        w: usize,
+        style: &RenderStyle,
+    ) {
+        fb.set_as(x, y, style.border_tee_left, Element::Border);
+        for i in 1..w - 1 {
+            fb.set_as(x + i, y, style.border_horizontal, Element::Border);
+        }
+        fb.set_as(x + w - 1, y, style.border_tee_right, Element::Border);
+    }
+}
+
+impl Widget for Panel {
+    fn size(&self) -> (usize, usize) {
+        let content: usize = self.children.iter().map(|c| c.size().1).sum();
+        let footer = if self.footer.is_some() { 2 } else { 0 };
+        // Borders, title row and its separator
+        (
+            self.inner_width() + 2 * Self::PADDING + 2,
+            content + footer + 4,
+        )
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, x: usize, y: usize, style: &RenderStyle) {
+        let (w, h) = self.size();
+        let inner = self.inner_width();
+
+        // Frame, blanking the interior so nothing underneath shows through
+        for row in 0..h {
+            for col in 0..w {
+                let ch = match (row, col) {
+                    (0, 0) => style.border_corner_tl,
+                    (0, c) if c == w - 1 => style.border_corner_tr,
+                    (r, 0) if r == h - 1 => style.border_corner_bl,
+                    (r, c) if r == h - 1 && c == w - 1 => style.border_corner_br,
+                    (0, _) => style.border_horizontal,
+                    (r, _) if r == h - 1 => style.border_horizontal,
+                    (_, 0) => style.border_vertical,
+                    (_, c) if c == w - 1 => style.border_vertical,
+                    _ => ' ',
+                };
+                let element = if ch == ' ' {
+                    Element::Text
+                } else {
+                    Element::Border
+                };
+                fb.set_as(x + col, y + row, ch, element);
+            }
+        }
+
+        // Centered title and separator
+        let content_x = x + 1 + Self::PADDING;
+        let title_x = content_x + (inner - str_width(&self.title)) / 2;
+        put_text(fb, title_x, y + 1, &self.title);
+        self.draw_separator(fb, x, y + 2, w, style);
+
+        // Children stacked top to bottom
+        let mut row = y + 3;
+        for child in &self.children {
+            child.draw(fb, content_x, row, style);
+            row += child.size().1;
+        }
+
+        // Footer hints, centered under their own separator
+        if let Some(footer) = &self.footer {
+            self.draw_separator(fb, x, row, w, style);
+            let footer_x = content_x + (inner - footer.size().0) / 2;
+            footer.draw(fb, footer_x, row + 1, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(widget: &dyn Widget, style: &RenderStyle) -> String {
+        widget.render(style).to_string_lines()
+    }
+
+    #[test]
+    fn label_and_hints_size_to_text() {
+        let style = RenderStyle::ascii();
+        assert_eq!(
+            snapshot(&Label::new("Board: 80×24"), &style),
+            "Board: 80×24\n"
+        );
+        let hints = KeyHints::new(&[("Space", "Resume"), ("Q", "Quit")]);
+        assert_eq!(snapshot(&hints, &style), "Space Resume  Q Quit\n");
+    }
+
+    #[test]
+    fn menu_marks_selected_item() {
+        let menu = Menu::new(&["Play", "Settings", "Quit"], 1);
+        assert_eq!(
+            snapshot(&menu, &RenderStyle::ascii()),
+            "  Play    \n> Settings\n  Quit    \n"
+        );
+        assert_eq!(
+            snapshot(&menu, &RenderStyle::unicode()),
+            "  Play    \n▶ Settings\n  Quit    \n"
+        );
+    }
+
+    #[test]
+    fn progress_bar_fills_proportionally() {
+        assert_eq!(
+            snapshot(&ProgressBar::new(3, 10, 10), &RenderStyle::ascii()),
+            "###.......\n"
+        );
+        assert_eq!(
+            snapshot(&ProgressBar::new(15, 10, 4), &RenderStyle::unicode()),
+            "████\n"
+        );
+        assert_eq!(
+            snapshot(&ProgressBar::new(1, 0, 3), &RenderStyle::ascii()),
+            "...\n"
+        );
+    }
+
+    #[test]
+    fn panel_ascii_snapshot() {
+        let panel = Panel::new("PAUSED")
+            .line("Score 3-2")
+            .gap(1)
+            .with(Menu::new(&["Resume", "Quit"], 0))
+            .footer(KeyHints::new(&[("Q", "Quit")]));
+        let expected = "\
++-------------+
+|   PAUSED    |
++-------------+
+|  Score 3-2  |
+|             |
+|  > Resume   |
+|    Quit     |
++-------------+
+|   Q Quit    |
++-------------+
+";
+        assert_eq!(snapshot(&panel, &RenderStyle::ascii()), expected);
+    }
+
+    #[test]
+    fn panel_unicode_snapshot() {
+        let panel = Panel::new("SETTINGS").with(ProgressBar::new(1, 2, 6));
+        let expected = "\
+┌────────────┐
+│  SETTINGS  │
+├────────────┤
+│  ███░░░    │
+└────────────┘
+";
+        assert_eq!(snapshot(&panel, &RenderStyle::unicode()), expected);
+    }
+}